@@ -0,0 +1,52 @@
+//! Compares looking up embedded files in a sorted table with a binary search,
+//! which is what the derive generates, with matching on the path, which is
+//! what it used to generate. Both use the files generated by `build.rs`.
+//!
+//! Run with `cargo bench --manifest-path bench/Cargo.toml`. Benchmarks are
+//! built in release mode, so `Large` embeds the files.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_embed_for_web::RustEmbed;
+use rust_embed_for_web_bench::Large;
+
+include!(concat!(env!("OUT_DIR"), "/find_with_match.rs"));
+
+fn find_with_binary_search(path: &str) -> Option<usize> {
+    Large::embedded_files()
+        .binary_search_by(|(file_path, _)| (*file_path).cmp(path))
+        .ok()
+}
+
+fn lookup(c: &mut Criterion) {
+    let files = Large::embedded_files();
+    let paths = [
+        files[0].0,
+        files[files.len() / 2].0,
+        files[files.len() - 1].0,
+        "dir00/missing.txt",
+    ];
+    for path in paths {
+        assert_eq!(find_with_match(path), find_with_binary_search(path));
+    }
+
+    let mut group = c.benchmark_group("find");
+    for path in paths {
+        group.bench_function(format!("match {:?}", path), |b| {
+            b.iter(|| find_with_match(black_box(path)))
+        });
+        group.bench_function(format!("binary search {:?}", path), |b| {
+            b.iter(|| find_with_binary_search(black_box(path)))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get");
+    for path in paths {
+        group.bench_function(format!("{:?}", path), |b| {
+            b.iter(|| Large::get(black_box(path)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+//! Generates the fixture for the benchmarks: a folder with many files, and a
+//! function that finds them with a `match` on the path, which is how the
+//! derive used to look up files.
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUST_EMBED_BENCH_FILES");
+    let count: usize = match env::var("RUST_EMBED_BENCH_FILES") {
+        Ok(value) => value
+            .parse()
+            .expect("RUST_EMBED_BENCH_FILES must be a number"),
+        Err(_) => 4000,
+    };
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let public = out_dir.join("public");
+    let _ = fs::remove_dir_all(&public);
+    let mut paths = Vec::with_capacity(count);
+    for i in 0..count {
+        let path = format!("dir{:02}/file{:05}.txt", i % 40, i);
+        let full_path = public.join(&path);
+        fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        fs::write(
+            &full_path,
+            format!("This is file number {}.\n", i).repeat(20),
+        )
+        .unwrap();
+        paths.push(path);
+    }
+    // The same order as the table that the derive generates
+    paths.sort();
+
+    let mut find =
+        String::from("fn find_with_match(path: &str) -> Option<usize> {\n    match path {\n");
+    for (index, path) in paths.iter().enumerate() {
+        writeln!(find, "        {:?} => Some({}),", path, index).unwrap();
+    }
+    find.push_str("        _ => None,\n    }\n}\n");
+    fs::write(out_dir.join("find_with_match.rs"), find).unwrap();
+}
@@ -0,0 +1,22 @@
+//! A large embed to benchmark with. The files are generated by `build.rs`,
+//! set `RUST_EMBED_BENCH_FILES` to change how many there are.
+//!
+//! To measure how long the embed takes to compile, build it in release mode
+//! with the compression cache disabled, so that every build does the same
+//! work:
+//!
+//! ```sh
+//! cargo build --release --manifest-path bench/Cargo.toml
+//! touch bench/src/lib.rs
+//! RUST_EMBED_NO_CACHE=1 cargo build --release --manifest-path bench/Cargo.toml --timings
+//! ```
+//!
+//! The time of `rust-embed-for-web-bench` in the report is the time of the
+//! embed. This crate only uses the derive, so to compare with the `match` that
+//! older versions generated, copy the `bench` folder into a checkout of an
+//! older version and run the same commands there.
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$OUT_DIR/public"]
+pub struct Large;
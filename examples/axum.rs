@@ -0,0 +1,14 @@
+use axum::Router;
+use rust_embed_for_web::{RustEmbed, ServeEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+struct Asset;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    println!("Launching server at http://127.0.0.1:8000/dist/index.html");
+    let app = Router::new().nest_service("/dist", ServeEmbed::<Asset>::new());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+    axum::serve(listener, app).await
+}
@@ -1,5 +1,9 @@
+use std::{fmt::Display, str::FromStr};
+
 use rust_embed_for_web_utils::Config;
-use syn::{Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue};
+use syn::{
+    punctuated::Punctuated, Attribute, Expr, ExprLit, Lit, LitStr, Meta, MetaNameValue, Token,
+};
 
 fn parse_str(attribute: &Attribute) -> Option<String> {
     if let Meta::NameValue(MetaNameValue {
@@ -15,6 +19,18 @@ fn parse_str(attribute: &Attribute) -> Option<String> {
     None
 }
 
+/// Parse attributes with a pair of strings like `#[name("first", "second")]`.
+fn parse_str_pair(attribute: &Attribute) -> Option<(String, String)> {
+    let values = attribute
+        .parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)
+        .ok()?;
+    let mut values = values.iter();
+    match (values.next(), values.next(), values.next()) {
+        (Some(first), Some(second), None) => Some((first.value(), second.value())),
+        _ => None,
+    }
+}
+
 fn parse_bool(attribute: &Attribute) -> Option<bool> {
     if let Meta::NameValue(MetaNameValue {
         value: Expr::Lit(ExprLit {
@@ -29,6 +45,39 @@ fn parse_bool(attribute: &Attribute) -> Option<bool> {
     None
 }
 
+fn parse_int<N>(attribute: &Attribute) -> Option<N>
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    if let Meta::NameValue(MetaNameValue {
+        value: Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }),
+        ..
+    }) = &attribute.meta
+    {
+        return value.base10_parse().ok();
+    }
+    None
+}
+
+fn parse_float(attribute: &Attribute) -> Option<f64> {
+    if let Meta::NameValue(MetaNameValue {
+        value: Expr::Lit(ExprLit { lit, .. }),
+        ..
+    }) = &attribute.meta
+    {
+        return match lit {
+            Lit::Float(value) => value.base10_parse().ok(),
+            Lit::Int(value) => value.base10_parse().ok(),
+            _ => None,
+        };
+    }
+    None
+}
+
 pub(crate) fn read_attribute_config(ast: &syn::DeriveInput) -> Config {
     let mut config = Config::default();
 
@@ -42,11 +91,66 @@ pub(crate) fn read_attribute_config(ast: &syn::DeriveInput) -> Config {
                 "exclude" => parse_str(attribute).map(|v| config.add_exclude(v)),
                 "gzip" => parse_bool(attribute).map(|v| config.set_gzip(v)),
                 "br" => parse_bool(attribute).map(|v| config.set_br(v)),
+                #[cfg(feature = "zstd")]
+                "zstd" => parse_bool(attribute).map(|v| config.set_zstd(v)),
+                #[cfg(not(feature = "zstd"))]
+                "zstd" => {
+                    if parse_bool(attribute) == Some(true) {
+                        panic!("#[zstd = true] requires the `zstd` feature of rust-embed-for-web");
+                    }
+                    None
+                }
+                "gzip_level" => parse_int(attribute).map(|v| config.set_gzip_level(v)),
+                #[cfg(feature = "zopfli")]
+                "zopfli" => parse_bool(attribute).map(|v| config.set_zopfli(v)),
+                #[cfg(not(feature = "zopfli"))]
+                "zopfli" => {
+                    if parse_bool(attribute) == Some(true) {
+                        panic!(
+                            "#[zopfli = true] requires the `zopfli` feature of rust-embed-for-web"
+                        );
+                    }
+                    None
+                }
+                "br_quality" => parse_int(attribute).map(|v| config.set_br_quality(v)),
+                "br_window" => parse_int(attribute).map(|v| config.set_br_window(v)),
+                "zstd_level" => parse_int(attribute).map(|v| config.set_zstd_level(v)),
+                "compression_min_ratio" => {
+                    parse_float(attribute).map(|v| config.set_compression_min_ratio(v))
+                }
                 "preserve_source" => parse_bool(attribute).map(|v| config.set_preserve_source(v)),
                 #[cfg(feature = "include-exclude")]
                 "preserve_source_except" => {
                     parse_str(attribute).map(|v| config.add_preserve_source_except(v))
                 }
+                "fallback" => parse_str(attribute).map(|v| config.set_fallback(v)),
+                "fallback_extensionless_only" => {
+                    parse_bool(attribute).map(|v| config.set_fallback_extensionless_only(v))
+                }
+                "allow_overrides" => parse_bool(attribute).map(|v| config.set_allow_overrides(v)),
+                "override_env" => parse_str(attribute).map(|v| config.set_override_env(v)),
+                "hashed_names" => parse_bool(attribute).map(|v| config.set_hashed_names(v)),
+                "integrity" => parse_str(attribute).map(|v| config.set_integrity_algorithm(v)),
+                "last_modified" => parse_bool(attribute)
+                    .map(|v| config.set_last_modified(v))
+                    .or_else(|| parse_str(attribute).map(|v| config.set_last_modified_source(v))),
+                #[cfg(feature = "include-exclude")]
+                "cache_control" => parse_str_pair(attribute)
+                    .map(|(pattern, value)| config.add_cache_control(pattern, value)),
+                #[cfg(not(feature = "include-exclude"))]
+                "cache_control" => {
+                    panic!("#[cache_control] requires the `include-exclude` feature of rust-embed-for-web");
+                }
+                #[cfg(feature = "include-exclude")]
+                "mime" => parse_str_pair(attribute)
+                    .map(|(pattern, mime_type)| config.add_mime(pattern, mime_type)),
+                #[cfg(not(feature = "include-exclude"))]
+                "mime" => {
+                    panic!("#[mime] requires the `include-exclude` feature of rust-embed-for-web");
+                }
+                "default_mime" => parse_str(attribute).map(|v| config.set_default_mime(v)),
+                "sniff_mime" => parse_bool(attribute).map(|v| config.set_sniff_mime(v)),
+                "index_file" => parse_str(attribute).map(|v| config.set_index_file(v)),
                 _ => None,
             };
         }
@@ -2,10 +2,72 @@ use std::io::{BufReader, Write};
 
 use brotli::enc::BrotliEncoderParams;
 use flate2::{write::GzEncoder, Compression};
+use rust_embed_for_web_utils::Config;
+
+/// Mime types of formats that are already compressed. Compressing these again
+/// is a waste of build time, and barely if at all saves any space.
+const COMPRESSED_MIME_TYPES: &[&str] = &[
+    "application/gzip",
+    "application/pdf",
+    "application/vnd.rar",
+    "application/x-7z-compressed",
+    "application/x-bzip2",
+    "application/x-rar-compressed",
+    "application/x-xz",
+    "application/zip",
+    "application/zstd",
+    "font/woff",
+    "font/woff2",
+    "image/avif",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+];
+
+/// Check if a file with this mime type is already compressed, and shouldn't be
+/// compressed again.
+pub(crate) fn is_compressed_mime_type(mime_type: &str) -> bool {
+    // Ignore parameters like `; charset=utf-8`
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+    mime_type.starts_with("audio/")
+        || mime_type.starts_with("video/")
+        || COMPRESSED_MIME_TYPES.contains(&mime_type)
+}
+
+/// Only keep the compressed data if it is small enough compared to the
+/// original data, based on the `compression_min_ratio` in the config.
+fn keep_if_smaller(original: &[u8], compressed: Vec<u8>, config: &Config) -> Option<Vec<u8>> {
+    let max_size = original.len() as f64 * config.get_compression_min_ratio();
+    if (compressed.len() as f64) <= max_size {
+        Some(compressed)
+    } else {
+        None
+    }
+}
+
+/// The settings in the config that change the output of `compress_gzip`,
+/// which are part of the key of cached compressed files.
+pub(crate) fn gzip_settings(config: &Config) -> String {
+    #[cfg(feature = "zopfli")]
+    if config.should_zopfli() {
+        return format!("zopfli {}", config.get_compression_min_ratio());
+    }
+    format!(
+        "gzip {} {}",
+        config.get_gzip_level(),
+        config.get_compression_min_ratio()
+    )
+}
+
+pub(crate) fn compress_gzip(data: &[u8], config: &Config) -> Option<Vec<u8>> {
+    #[cfg(feature = "zopfli")]
+    if config.should_zopfli() {
+        return compress_zopfli(data, config);
+    }
 
-pub(crate) fn compress_gzip(data: &[u8]) -> Option<Vec<u8>> {
     let mut data_gzip: Vec<u8> = Vec::new();
-    let mut encoder = GzEncoder::new(&mut data_gzip, Compression::default());
+    let mut encoder = GzEncoder::new(&mut data_gzip, Compression::new(config.get_gzip_level()));
     encoder
         .write_all(data)
         .expect("Failed to compress gzip data");
@@ -13,17 +75,58 @@ pub(crate) fn compress_gzip(data: &[u8]) -> Option<Vec<u8>> {
         .finish()
         .expect("Failed to finish compression of gzip data");
 
-    Some(data_gzip)
+    keep_if_smaller(data, data_gzip, config)
+}
+
+#[cfg(feature = "zopfli")]
+fn compress_zopfli(data: &[u8], config: &Config) -> Option<Vec<u8>> {
+    let mut data_gzip: Vec<u8> = Vec::new();
+    zopfli::compress(
+        zopfli::Options::default(),
+        zopfli::Format::Gzip,
+        data,
+        &mut data_gzip,
+    )
+    .expect("Failed to compress gzip data with zopfli");
+    keep_if_smaller(data, data_gzip, config)
+}
+
+/// The settings in the config that change the output of `compress_br`.
+pub(crate) fn br_settings(config: &Config) -> String {
+    format!(
+        "br {} {} {}",
+        config.get_br_quality(),
+        config.get_br_window(),
+        config.get_compression_min_ratio()
+    )
 }
 
-pub(crate) fn compress_br(data: &[u8]) -> Option<Vec<u8>> {
+pub(crate) fn compress_br(data: &[u8], config: &Config) -> Option<Vec<u8>> {
     let mut data_read = BufReader::new(data);
     let mut data_br: Vec<u8> = Vec::new();
-    brotli::BrotliCompress(
-        &mut data_read,
-        &mut data_br,
-        &BrotliEncoderParams::default(),
+    let params = BrotliEncoderParams {
+        quality: config.get_br_quality() as i32,
+        lgwin: config.get_br_window() as i32,
+        ..Default::default()
+    };
+    brotli::BrotliCompress(&mut data_read, &mut data_br, &params)
+        .expect("Failed to compress br data");
+    keep_if_smaller(data, data_br, config)
+}
+
+/// The settings in the config that change the output of `compress_zstd`.
+#[cfg(feature = "zstd")]
+pub(crate) fn zstd_settings(config: &Config) -> String {
+    format!(
+        "zstd {} {}",
+        config.get_zstd_level(),
+        config.get_compression_min_ratio()
     )
-    .expect("Failed to compress br data");
-    Some(data_br)
+}
+
+#[cfg(feature = "zstd")]
+pub(crate) fn compress_zstd(data: &[u8], config: &Config) -> Option<Vec<u8>> {
+    let data_zstd =
+        zstd::encode_all(data, config.get_zstd_level()).expect("Failed to compress zstd data");
+    keep_if_smaller(data, data_zstd, config)
 }
@@ -3,7 +3,15 @@ use globset::GlobMatcher;
 use proc_macro2::TokenStream as TokenStream2;
 use rust_embed_for_web_utils::Config;
 
-use crate::embed::MakeEmbed;
+use crate::{
+    embed::MakeEmbed,
+    folders::{get_all_files, Folder},
+    last_modified::generate_dynamic_last_modified,
+    lookup::{
+        generate_get_or_fallback, generate_hashed_names_dynamic, generate_hashed_names_trait,
+        generate_index_path,
+    },
+};
 
 impl MakeEmbed for Vec<String> {
     fn make_embed(&self) -> TokenStream2 {
@@ -58,11 +66,65 @@ impl MakeEmbed for Config {
             #[cfg(not(feature = "include-exclude"))]
             quote! {}
         };
+        let cache_control_embed = {
+            #[cfg(feature = "include-exclude")]
+            {
+                let cache_controls = self.get_cache_controls();
+                if cache_controls.is_empty() {
+                    quote! {}
+                } else {
+                    let (patterns, values): (Vec<String>, Vec<String>) = cache_controls
+                        .iter()
+                        .map(|(matcher, value)| (matcher.glob().to_string(), value.clone()))
+                        .unzip();
+                    quote! {
+                        for (pattern, value) in &[#((#patterns, #values)),*] {
+                            config.add_cache_control(pattern.to_string(), value.to_string());
+                        }
+                    }
+                }
+            }
+            #[cfg(not(feature = "include-exclude"))]
+            quote! {}
+        };
+
+        let mime_embed = {
+            #[cfg(feature = "include-exclude")]
+            {
+                let mimes = self.get_mimes();
+                if mimes.is_empty() {
+                    quote! {}
+                } else {
+                    let (patterns, values): (Vec<String>, Vec<String>) = mimes
+                        .iter()
+                        .map(|(matcher, value)| (matcher.glob().to_string(), value.clone()))
+                        .unzip();
+                    quote! {
+                        for (pattern, value) in &[#((#patterns, #values)),*] {
+                            config.add_mime(pattern.to_string(), value.to_string());
+                        }
+                    }
+                }
+            }
+            #[cfg(not(feature = "include-exclude"))]
+            quote! {}
+        };
+        let default_mime_embed = match self.get_default_mime() {
+            Some(default_mime) => quote! {
+                config.set_default_mime(#default_mime.to_string());
+            },
+            None => quote! {},
+        };
+        let sniff_mime = self.should_sniff_mime();
 
         quote! {
             let mut config = rust_embed_for_web::utils::Config::new();
             #includes_embed
             #excludes_embed
+            #cache_control_embed
+            #mime_embed
+            #default_mime_embed
+            config.set_sniff_mime(#sniff_mime);
             config
         }
     }
@@ -71,24 +133,72 @@ impl MakeEmbed for Config {
 pub(crate) fn generate_dynamic_impl(
     ident: &syn::Ident,
     config: &Config,
-    folder_path: &str,
+    folders: &[Folder],
     prefix: &str,
 ) -> TokenStream2 {
+    // Files are read at runtime, but we still want to catch conflicts between
+    // the folders early
+    if folders.len() > 1 && !config.should_allow_overrides() {
+        get_all_files(folders, config);
+    }
+    let file_type = quote! { rust_embed_for_web::DynamicFile };
+    let get_or_fallback = generate_get_or_fallback(ident, config, prefix, &file_type);
+    let index_path = generate_index_path(config);
+    let get_name = if config.should_hash_names() {
+        format_ident!("get_unhashed")
+    } else {
+        format_ident!("get")
+    };
+    let hashed_names = generate_hashed_names_dynamic(ident, config);
+    let hashed_names_trait = generate_hashed_names_trait(ident, config);
+    let integrity_algorithm = config.get_integrity_algorithm().make_embed();
+    let last_modified = generate_dynamic_last_modified(config);
     let config = config.make_embed();
+    let folders = folders.make_embed();
 
     quote! {
       impl #ident {
-        fn get(path: &str) -> Option<rust_embed_for_web::DynamicFile> {
+        fn #get_name(path: &str) -> Option<rust_embed_for_web::DynamicFile> {
+          #index_path
           let config = { #config };
-          let path = path.strip_prefix(#prefix)?;
-          if config.should_include(path) {
-            let folder_path: std::path::PathBuf = std::convert::From::from(#folder_path);
-            let combined_path = folder_path.join(path);
-            rust_embed_for_web::DynamicFile::read_from_fs(combined_path).ok()
-          } else {
-            None
+          if !config.should_include(path) {
+            return None;
           }
+          let full_path = path;
+          let cache_control = config.get_cache_control(path).map(String::from);
+          // Later folders override the earlier ones
+          const FOLDERS: &[(&str, &str)] = #folders;
+          FOLDERS.iter().rev().find_map(|(folder_path, prefix)| {
+            let path = path.strip_prefix(prefix)?;
+            let combined_path = rust_embed_for_web::utils::resolve_in_folder(folder_path, path)?;
+            let file = rust_embed_for_web::DynamicFile::read_from_fs(combined_path).ok()?;
+            #last_modified
+            let data = rust_embed_for_web::EmbedableFile::data(&file).unwrap_or_default();
+            let mime_type = rust_embed_for_web::utils::guess_mime_type(&config, full_path, &data);
+            Some(
+              file.with_integrity_algorithm(#integrity_algorithm)
+                .with_cache_control(cache_control.clone())
+                .with_mime_type(mime_type),
+            )
+          })
+        }
+
+        fn paths() -> rust_embed_for_web::Filenames {
+          let config = { #config };
+          const FOLDERS: &[(&str, &str)] = #folders;
+          let mut seen = std::collections::HashSet::new();
+          let paths: Vec<String> = FOLDERS
+            .iter()
+            .flat_map(|(folder_path, prefix)| rust_embed_for_web::utils::get_files(folder_path, &config, prefix))
+            .map(|entry| entry.rel_path)
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+          rust_embed_for_web::Filenames::Dynamic(paths.into_iter())
         }
+
+        #get_or_fallback
+
+        #hashed_names
       }
 
       impl rust_embed_for_web::RustEmbed for #ident {
@@ -97,6 +207,16 @@ pub(crate) fn generate_dynamic_impl(
         fn get(file_path: &str) -> Option<Self::File> {
           #ident::get(file_path)
         }
+
+        fn paths() -> rust_embed_for_web::Filenames {
+          #ident::paths()
+        }
+
+        fn get_or_fallback(file_path: &str) -> Option<Self::File> {
+          #ident::get_or_fallback(file_path)
+        }
+
+        #hashed_names_trait
       }
     }
 }
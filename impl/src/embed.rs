@@ -1,9 +1,23 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use proc_macro2::TokenStream as TokenStream2;
-use rust_embed_for_web_utils::{get_files, Config, DynamicFile, EmbedableFile, FileEntry};
+use rust_embed_for_web_utils::{
+    guess_mime_type, Config, DynamicFile, EmbedableFile, FileEntry, IntegrityAlgorithm,
+};
 
-use crate::compress::{compress_br, compress_gzip};
+use crate::compress::{
+    br_settings, compress_br, compress_gzip, gzip_settings, is_compressed_mime_type,
+};
+#[cfg(feature = "zstd")]
+use crate::compress::{compress_zstd, zstd_settings};
+use crate::folders::{get_all_files, track_folders, Folder};
+use crate::generated::{compress_cached, GeneratedData};
+use crate::jobs::parallel_map;
+use crate::last_modified::{generate_dynamic_last_modified, LastModified};
+use crate::lookup::{
+    generate_get_or_fallback, generate_hashed_names_embed, generate_hashed_names_trait,
+    generate_index_aliases, generate_index_path,
+};
 
 /// Anything that can be embedded into the program.
 ///
@@ -14,19 +28,28 @@ pub(crate) trait MakeEmbed {
     fn make_embed(&self) -> TokenStream2;
 }
 
-impl MakeEmbed for Vec<u8> {
+/// A file that the generated code includes with `include_bytes!`.
+///
+/// This is a lot faster to compile than writing out the bytes, and it also
+/// makes cargo rebuild the embed when the file changes.
+struct IncludeBytes(String);
+
+impl MakeEmbed for IncludeBytes {
     fn make_embed(&self) -> TokenStream2 {
-        // Not sure why quote doesn't like it if I use #self here
-        let v = self;
-        quote! { &[#(#v),*] }
+        let path = &self.0;
+        quote! { include_bytes!(#path) }
     }
 }
 
-impl MakeEmbed for Cow<'static, [u8]> {
+impl MakeEmbed for GeneratedData {
     fn make_embed(&self) -> TokenStream2 {
-        // We need to convert Cow to &[u8] to use it in the quote! macro
-        let v: &[u8] = self.as_ref();
-        quote! { &[#(#v),*] }
+        match self {
+            GeneratedData::File(path) => IncludeBytes(path.clone()).make_embed(),
+            GeneratedData::Inline(data) => {
+                let data = proc_macro2::Literal::byte_string(data);
+                quote! { #data }
+            }
+        }
     }
 }
 
@@ -44,6 +67,23 @@ impl MakeEmbed for i64 {
     }
 }
 
+impl MakeEmbed for String {
+    fn make_embed(&self) -> TokenStream2 {
+        quote! { #self }
+    }
+}
+
+impl MakeEmbed for IntegrityAlgorithm {
+    fn make_embed(&self) -> TokenStream2 {
+        let variant = match self {
+            IntegrityAlgorithm::Sha256 => quote! { Sha256 },
+            IntegrityAlgorithm::Sha384 => quote! { Sha384 },
+            IntegrityAlgorithm::Sha512 => quote! { Sha512 },
+        };
+        quote! { rust_embed_for_web::IntegrityAlgorithm::#variant }
+    }
+}
+
 impl<T: MakeEmbed> MakeEmbed for Option<T> {
     fn make_embed(&self) -> TokenStream2 {
         match self {
@@ -56,51 +96,125 @@ impl<T: MakeEmbed> MakeEmbed for Option<T> {
     }
 }
 
-struct EmbedDynamicFile<'t> {
-    file: &'t DynamicFile,
-    config: &'t Config,
-    rel_path: &'t str,
+/// A file that was read, hashed and compressed, and is ready to be embedded.
+///
+/// Preparing files is the slow part of embedding them, so it's done on several
+/// threads. Creating the tokens has to happen on the thread of the macro.
+struct PreparedFile {
+    rel_path: String,
+    full_canonical_path: String,
+    file: DynamicFile,
+    data_gzip: Option<GeneratedData>,
+    data_br: Option<GeneratedData>,
+    data_zstd: Option<GeneratedData>,
+    integrity: String,
+    preserve_source: bool,
+    cache_control: Option<String>,
 }
 
-impl<'t> EmbedDynamicFile<'t> {
-    fn new(file: &'t DynamicFile, config: &'t Config, rel_path: &'t str) -> EmbedDynamicFile<'t> {
-        EmbedDynamicFile {
-            file,
-            config,
+impl PreparedFile {
+    fn prepare(
+        entry: FileEntry,
+        config: &Config,
+        last_modified: &LastModified,
+    ) -> Option<PreparedFile> {
+        let FileEntry {
             rel_path,
-        }
-    }
-}
-
-impl<'t> MakeEmbed for EmbedDynamicFile<'t> {
-    fn make_embed(&self) -> TokenStream2 {
-        let file = self.file;
-        let name = file.name().make_embed();
+            full_canonical_path,
+        } = entry;
+        let file = DynamicFile::read_from_fs(&full_canonical_path).ok()?;
+        let timestamp = last_modified.timestamp(&full_canonical_path, &file);
+        let file = file.with_last_modified_timestamp(timestamp);
         // safety: `data()` will always return `Some` for dynamic files
         let data = file.data().unwrap();
-        let data_gzip = if self.config.should_gzip() {
-            compress_gzip(data.as_ref()).make_embed()
+        // The mime type depends on the path of the file in the embed, which
+        // may be different from the path on disk.
+        let mime_type = guess_mime_type(config, &rel_path, &data);
+        let file = file.with_mime_type(mime_type);
+        let compressible = !file
+            .mime_type()
+            .map(|v| is_compressed_mime_type(&v))
+            .unwrap_or(false);
+        let data_gzip = if compressible && config.should_gzip() {
+            compress_cached(&data, &gzip_settings(config), "gz", || {
+                compress_gzip(&data, config)
+            })
         } else {
-            None::<Vec<u8>>.make_embed()
+            None
         };
-        let data_br = if self.config.should_br() {
-            compress_br(data.as_ref()).make_embed()
+        let data_br = if compressible && config.should_br() {
+            compress_cached(&data, &br_settings(config), "br", || {
+                compress_br(&data, config)
+            })
         } else {
-            None::<Vec<u8>>.make_embed()
+            None
         };
+        #[cfg(feature = "zstd")]
+        let data_zstd = if compressible && config.should_zstd() {
+            compress_cached(&data, &zstd_settings(config), "zst", || {
+                compress_zstd(&data, config)
+            })
+        } else {
+            None
+        };
+        #[cfg(not(feature = "zstd"))]
+        let data_zstd = None;
+        let integrity = config.get_integrity_algorithm().integrity(&data);
         // for example, preserve_source = false, preserve_source_except = "*.html"
         // will only preserve source for files that end with `.html`.
-        let mut preserve_source = self.config.should_preserve_source();
-        if self.config.is_preserve_source_except(self.rel_path) {
+        let mut preserve_source = config.should_preserve_source();
+        if config.is_preserve_source_except(&rel_path) {
             preserve_source = !preserve_source;
         }
-        let data = if preserve_source { Some(data) } else { None };
-        let data = data.make_embed();
+        // Without a compressed version, the source is the only way to serve
+        // the file.
+        if data_gzip.is_none() && data_br.is_none() && data_zstd.is_none() {
+            preserve_source = true;
+        }
+        let cache_control = config.get_cache_control(&rel_path).map(String::from);
+        Some(PreparedFile {
+            rel_path,
+            full_canonical_path,
+            file,
+            data_gzip,
+            data_br,
+            data_zstd,
+            integrity,
+            preserve_source,
+            cache_control,
+        })
+    }
+}
+
+impl MakeEmbed for PreparedFile {
+    fn make_embed(&self) -> TokenStream2 {
+        let file = &self.file;
+        let name = file.name().make_embed();
+        let len = file.len();
+        let data = if self.preserve_source {
+            Some(IncludeBytes(self.full_canonical_path.clone())).make_embed()
+        } else {
+            // Include the file even though it isn't embedded, so cargo still
+            // rebuilds the embed when it changes. Constants that are never
+            // used don't end up in the executable.
+            let full_canonical_path = &self.full_canonical_path;
+            quote! {
+                {
+                    const _: &[u8] = include_bytes!(#full_canonical_path);
+                    None
+                }
+            }
+        };
+        let data_gzip = self.data_gzip.make_embed();
+        let data_br = self.data_br.make_embed();
+        let data_zstd = self.data_zstd.make_embed();
         let hash = file.hash().make_embed();
         let etag = file.etag().make_embed();
+        let integrity = self.integrity.make_embed();
         let last_modified = file.last_modified().make_embed();
         let last_modified_timestamp = file.last_modified_timestamp().make_embed();
         let mime_type = file.mime_type().make_embed();
+        let cache_control = self.cache_control.make_embed();
         // Make sure that the order of these parameters is correct!
         quote! {
             rust_embed_for_web::EmbeddedFile::__internal_make(
@@ -108,57 +222,168 @@ impl<'t> MakeEmbed for EmbedDynamicFile<'t> {
                 #data,
                 #data_gzip,
                 #data_br,
+                #data_zstd,
+                #len,
                 #hash,
                 #etag,
+                #integrity,
                 #last_modified,
                 #last_modified_timestamp,
                 #mime_type,
+                #cache_control,
             )
         }
     }
 }
 
+/// Combine the aliases into a table sorted by path, which points to the index
+/// of the file in `paths`.
+///
+/// Files win over aliases with the same path, and index files win over hashed
+/// names.
+fn sort_aliases(
+    paths: &[String],
+    index_aliases: Vec<(String, String)>,
+    hashed_aliases: Vec<(String, String)>,
+) -> (Vec<String>, Vec<usize>) {
+    let mut aliases: BTreeMap<String, usize> = BTreeMap::new();
+    for (alias, path) in index_aliases.into_iter().chain(hashed_aliases) {
+        if paths.binary_search(&alias).is_ok() {
+            continue;
+        }
+        if let Ok(index) = paths.binary_search(&path) {
+            aliases.entry(alias).or_insert(index);
+        }
+    }
+    aliases.into_iter().unzip()
+}
+
 pub(crate) fn generate_embed_impl(
     ident: &syn::Ident,
     config: &Config,
-    folder_path: &str,
+    folders: &[Folder],
     prefix: &str,
 ) -> TokenStream2 {
-    let embeds: Vec<TokenStream2> = get_files(folder_path, config, prefix)
-        .filter_map(
-            |FileEntry {
-                 rel_path,
-                 full_canonical_path,
-             }| {
-                if let Ok(file) = DynamicFile::read_from_fs(full_canonical_path) {
-                    let file_embed =
-                        EmbedDynamicFile::new(&file, config, rel_path.as_str()).make_embed();
-                    Some(quote! {
-                        #rel_path => Some(#file_embed),
-                    })
-                } else {
-                    None
+    track_folders(folders);
+    let last_modified = LastModified::new(config, folders);
+    let mut files = get_all_files(folders, config);
+    // The files are looked up with a binary search, so they must be sorted
+    files.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    let prepared = parallel_map(files, |entry| {
+        PreparedFile::prepare(entry, config, &last_modified)
+    });
+    let ((paths, hashes), embeds): ((Vec<String>, Vec<String>), Vec<TokenStream2>) = prepared
+        .into_iter()
+        .flatten()
+        .map(|file| {
+            let embed = file.make_embed();
+            ((file.rel_path, file.file.hash().into_owned()), embed)
+        })
+        .unzip();
+
+    let (file_type, get) = match config.get_override_env() {
+        Some(override_env) => {
+            let index_path = generate_index_path(config);
+            let integrity_algorithm = config.get_integrity_algorithm().make_embed();
+            let dynamic_last_modified = generate_dynamic_last_modified(config);
+            (
+                quote! { rust_embed_for_web::EmbeddedFileImpl },
+                quote! {
+                    fn get(path: &str) -> Option<rust_embed_for_web::EmbeddedFileImpl> {
+                        #index_path
+                        // Aliases like hashed names are overridden by the
+                        // file they point to
+                        let (path, file) = #ident::find_embedded(path)?;
+                        let cache_control = rust_embed_for_web::EmbedableFile::cache_control(&file)
+                            .map(|v| v.into_owned());
+                        let mime_type = rust_embed_for_web::EmbedableFile::mime_type(&file)
+                            .map(|v| v.into_owned());
+                        // Only files that are embedded can be overridden
+                        let override_file = std::env::var_os(#override_env).and_then(|folder_path| {
+                            let path = path.strip_prefix(#prefix)?;
+                            let combined_path = rust_embed_for_web::utils::resolve_in_folder(folder_path, path)?;
+                            let file = rust_embed_for_web::DynamicFile::read_from_fs(combined_path).ok()?;
+                            #dynamic_last_modified
+                            Some(
+                                file.with_integrity_algorithm(#integrity_algorithm)
+                                    .with_cache_control(cache_control)
+                                    .with_mime_type(mime_type),
+                            )
+                        });
+                        Some(match override_file {
+                            Some(override_file) => override_file.into(),
+                            None => file.into(),
+                        })
+                    }
+                },
+            )
+        }
+        None => (
+            quote! { rust_embed_for_web::EmbeddedFile },
+            quote! {
+                fn get(path: &str) -> Option<rust_embed_for_web::EmbeddedFile> {
+                    #ident::find_embedded(path).map(|(_, file)| file)
                 }
             },
-        )
-        .collect();
+        ),
+    };
+    let get_or_fallback = generate_get_or_fallback(ident, config, prefix, &file_type);
+    let index_aliases = generate_index_aliases(config, &paths);
+    let (hashed_aliases, hashed_names) =
+        generate_hashed_names_embed(ident, config, &paths, &hashes);
+    let hashed_names_trait = generate_hashed_names_trait(ident, config);
+    let (alias_paths, alias_indexes) = sort_aliases(&paths, index_aliases, hashed_aliases);
+    let files_len = paths.len();
+    let aliases_len = alias_paths.len();
 
     quote! {
+      // This makes cargo rebuild the embed when SOURCE_DATE_EPOCH changes
+      const _: Option<&str> = option_env!("SOURCE_DATE_EPOCH");
+
       impl #ident {
-          fn get(path: &str) -> Option<rust_embed_for_web::EmbeddedFile> {
-              match path {
-                    #(#embeds)*
-                    _ => None,
-              }
+          /// All embedded files along with their paths, sorted by path.
+          pub fn embedded_files() -> &'static [(&'static str, rust_embed_for_web::EmbeddedFile)] {
+              static FILES: [(&str, rust_embed_for_web::EmbeddedFile); #files_len] = [
+                  #((#paths, #embeds)),*
+              ];
+              &FILES
+          }
+
+          fn find_embedded(path: &str) -> Option<(&'static str, rust_embed_for_web::EmbeddedFile)> {
+              static ALIASES: [(&str, usize); #aliases_len] = [
+                  #((#alias_paths, #alias_indexes)),*
+              ];
+              rust_embed_for_web::EmbeddedFile::__internal_find(#ident::embedded_files(), &ALIASES, path)
+          }
+
+          #get
+
+          fn paths() -> rust_embed_for_web::Filenames {
+              const PATHS: &[&str] = &[#(#paths),*];
+              rust_embed_for_web::Filenames::Embedded(PATHS.iter())
           }
+
+          #get_or_fallback
+
+          #hashed_names
       }
 
       impl rust_embed_for_web::RustEmbed for #ident {
-        type File = rust_embed_for_web::EmbeddedFile;
+        type File = #file_type;
 
         fn get(file_path: &str) -> Option<Self::File> {
           #ident::get(file_path)
         }
+
+        fn paths() -> rust_embed_for_web::Filenames {
+          #ident::paths()
+        }
+
+        fn get_or_fallback(file_path: &str) -> Option<Self::File> {
+          #ident::get_or_fallback(file_path)
+        }
+
+        #hashed_names_trait
       }
     }
 }
@@ -0,0 +1,157 @@
+use std::{collections::HashMap, env, path::Path};
+
+use proc_macro2::TokenStream as TokenStream2;
+use rust_embed_for_web_utils::{get_files, Config, FileEntry};
+use syn::{parse::ParseStream, Attribute, Expr, ExprLit, Ident, Lit, LitStr, Meta, MetaNameValue};
+
+use crate::embed::MakeEmbed;
+
+/// One of the folders that are embedded.
+pub(crate) struct Folder {
+    /// The absolute path to the folder.
+    pub(crate) path: String,
+    /// The prefix for the files in this folder. This already includes the
+    /// `prefix` of the whole embed.
+    pub(crate) prefix: String,
+}
+
+impl MakeEmbed for [Folder] {
+    fn make_embed(&self) -> TokenStream2 {
+        let folders = self
+            .iter()
+            .map(|Folder { path, prefix }| quote! { (#path, #prefix) });
+        quote! { &[#(#folders),*] }
+    }
+}
+
+/// Parse a `#[folder("path", prefix = "prefix/")]` attribute.
+fn parse_folder_list(input: ParseStream) -> syn::Result<(String, String)> {
+    let path: LitStr = input.parse()?;
+    let mut prefix = String::new();
+    if input.parse::<Option<syn::Token![,]>>()?.is_some() && !input.is_empty() {
+        let name: Ident = input.parse()?;
+        if name != "prefix" {
+            return Err(syn::Error::new(name.span(), "expected `prefix = \"...\"`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        prefix = input.parse::<LitStr>()?.value();
+        input.parse::<Option<syn::Token![,]>>()?;
+    }
+    Ok((path.value(), prefix))
+}
+
+fn parse_folder(attribute: &Attribute) -> (String, String) {
+    match &attribute.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(path),
+                    ..
+                }),
+            ..
+        }) => (path.value(), String::new()),
+        Meta::List(_) => attribute
+            .parse_args_with(parse_folder_list)
+            .unwrap_or_else(|err| panic!("#[derive(RustEmbed)] invalid folder attribute: {}", err)),
+        _ => panic!(
+            "#[derive(RustEmbed)] folder must be either #[folder = \"path\"] or #[folder(\"path\", prefix = \"prefix/\")]"
+        ),
+    }
+}
+
+/// Read all the `folder` attributes, in the order they were written.
+pub(crate) fn read_folders(ast: &syn::DeriveInput, prefix: &str) -> Vec<Folder> {
+    let folders: Vec<Folder> = ast
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("folder"))
+        .map(|attribute| {
+            let (path, folder_prefix) = parse_folder(attribute);
+            #[cfg(feature = "interpolate-folder-path")]
+            let path = shellexpand::full(&path).unwrap().to_string();
+
+            // Base relative paths on the Cargo.toml location
+            let path = if Path::new(&path).is_relative() {
+                Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+                    .join(path)
+                    .to_str()
+                    .unwrap()
+                    .to_owned()
+            } else {
+                path
+            };
+
+            Folder {
+                path,
+                prefix: format!("{}{}", prefix, folder_prefix),
+            }
+        })
+        .collect();
+    if folders.is_empty() {
+        panic!("#[derive(RustEmbed)] must contain at least one folder attribute");
+    }
+    folders
+}
+
+/// Check if a file exists at the path, which includes the prefix, in any of the
+/// folders.
+pub(crate) fn file_exists(folders: &[Folder], path: &str) -> bool {
+    folders.iter().any(|folder| {
+        path.strip_prefix(folder.prefix.as_str())
+            .map(|path| Path::new(&folder.path).join(path).is_file())
+            .unwrap_or(false)
+    })
+}
+
+/// Make the compiler rebuild the embed when files are added to or removed
+/// from the folders.
+///
+/// Cargo already rebuilds it when an embedded file changes, because the files
+/// are included with `include_bytes!`. Only the directories themselves change
+/// when files are added or removed, and telling the compiler about them needs
+/// the nightly `proc_macro::tracked` API, which is enabled with
+/// `--cfg rust_embed_for_web_tracked_path`.
+#[cfg(rust_embed_for_web_tracked_path)]
+pub(crate) fn track_folders(folders: &[Folder]) {
+    for folder in folders {
+        walkdir::WalkDir::new(&folder.path)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_dir())
+            .for_each(|e| proc_macro::tracked::path(e.path()));
+    }
+}
+
+#[cfg(not(rust_embed_for_web_tracked_path))]
+pub(crate) fn track_folders(_folders: &[Folder]) {}
+
+/// Find all files in the folders.
+///
+/// If the same path exists in several folders, the file from the folder that
+/// was written last wins. Unless overrides are allowed in the config, this is
+/// a compile error instead.
+pub(crate) fn get_all_files(folders: &[Folder], config: &Config) -> Vec<FileEntry> {
+    let mut files: Vec<FileEntry> = vec![];
+    let mut found_in: HashMap<String, (usize, &str)> = HashMap::new();
+    for folder in folders {
+        for entry in get_files(&folder.path, config, &folder.prefix) {
+            match found_in.get(&entry.rel_path) {
+                Some((_, other_folder)) if !config.should_allow_overrides() => panic!(
+                    "#[derive(RustEmbed)] file {} exists in both {} and {}, add #[allow_overrides = true] if the later folder should override it",
+                    entry.rel_path, other_folder, folder.path
+                ),
+                Some((index, _)) => {
+                    let index = *index;
+                    found_in.insert(entry.rel_path.clone(), (index, &folder.path));
+                    files[index] = entry;
+                }
+                None => {
+                    found_in.insert(entry.rel_path.clone(), (files.len(), &folder.path));
+                    files.push(entry);
+                }
+            }
+        }
+    }
+    files
+}
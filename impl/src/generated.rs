@@ -0,0 +1,351 @@
+use std::{
+    env,
+    fs::{self, DirBuilder},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use sha2::{Digest, Sha256};
+
+/// Data that the generated code embeds.
+#[derive(Debug, PartialEq)]
+pub(crate) enum GeneratedData {
+    /// A file that the generated code includes with `include_bytes!`, which is
+    /// a lot faster to compile.
+    File(String),
+    /// The data itself, for when there's nowhere to write the file to.
+    Inline(Vec<u8>),
+}
+
+/// The folder where files that the generated code includes are written, like
+/// the compressed versions of embedded files.
+///
+/// This is inside `OUT_DIR` if the crate has a build script, otherwise inside
+/// the data directory.
+fn generated_dir() -> Option<PathBuf> {
+    if let Some(out_dir) = env::var_os("OUT_DIR") {
+        return Some(PathBuf::from(out_dir).join("rust-embed-for-web"));
+    }
+    Some(data_dir()?.join("generated"))
+}
+
+/// The folder where we keep data across builds.
+///
+/// This is inside the target directory if `CARGO_TARGET_DIR` is set. Cargo
+/// doesn't tell proc macros where the target directory is otherwise, and
+/// guessing could write into the sources of a dependency, which may be read
+/// only. The cache directory of the user is used instead, because anything
+/// shared between users, like the temporary directory, could be written to by
+/// someone else.
+fn data_dir() -> Option<PathBuf> {
+    if let Some(target_dir) = env::var_os("CARGO_TARGET_DIR") {
+        // A relative target directory is relative to where cargo runs, which
+        // is also where it runs rustc
+        let target_dir = PathBuf::from(target_dir);
+        let target_dir = env::current_dir()
+            .map(|cwd| cwd.join(&target_dir))
+            .unwrap_or(target_dir);
+        return Some(target_dir.join("rust-embed-for-web"));
+    }
+    let absolute_var = |name: &str| {
+        env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    let cache_dir = absolute_var("XDG_CACHE_HOME")
+        .or_else(|| absolute_var("HOME").map(|home| home.join(".cache")))
+        .or_else(|| absolute_var("LOCALAPPDATA"))?;
+    Some(cache_dir.join("rust-embed-for-web"))
+}
+
+/// Create a folder, along with its parents, that only the current user can
+/// access.
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(path)
+}
+
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Write a file atomically, so that other builds running at the same time
+/// never see a partially written file.
+fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        create_private_dir(parent)?;
+    }
+    let temp_path = path.with_extension(format!(
+        "tmp-{}-{}",
+        process::id(),
+        TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// Write data into a file in `dir` named after its hash, unless the file
+/// already has exactly that data. Returns the path of the file.
+fn write_content_addressed(dir: &Path, data: &[u8], extension: &str) -> std::io::Result<String> {
+    let hash = format!("{:x}", Sha256::digest(data));
+    let path = dir.join(&hash[..2]).join(format!("{}.{}", hash, extension));
+    let is_written = fs::read(&path)
+        .map(|written| written == data)
+        .unwrap_or(false);
+    if !is_written {
+        write_atomic(&path, data)?;
+    }
+    path.into_os_string()
+        .into_string()
+        .map_err(|_| std::io::Error::other("path does not have a string representation"))
+}
+
+/// Write data into a file named after its hash, so that the generated code
+/// can include it with `include_bytes!`.
+///
+/// Files with the same contents are only written once, even across builds.
+/// If the file can't be written, the data is embedded inline instead.
+pub(crate) fn write_generated(data: &[u8], extension: &str) -> GeneratedData {
+    generated_dir()
+        .and_then(|dir| write_content_addressed(&dir, data, extension).ok())
+        .map(GeneratedData::File)
+        .unwrap_or_else(|| GeneratedData::Inline(data.to_vec()))
+}
+
+/// The folder where compressed files are cached between builds, or `None` if
+/// the cache is disabled with the `RUST_EMBED_NO_CACHE` environment variable.
+fn cache_dir() -> Option<PathBuf> {
+    match env::var_os("RUST_EMBED_NO_CACHE") {
+        Some(value) if !value.is_empty() => None,
+        _ => Some(data_dir()?.join("cache")),
+    }
+}
+
+/// Compress data with `compress`, or reuse the compressed data from an
+/// earlier build. Returns the compressed data for the generated code to
+/// include, or `None` if the data wasn't worth compressing.
+///
+/// The cache is keyed by the hash of the data and the `settings` that change
+/// the output of `compress`, so changing either compresses the data again.
+pub(crate) fn compress_cached<F>(
+    data: &[u8],
+    settings: &str,
+    extension: &str,
+    compress: F,
+) -> Option<GeneratedData>
+where
+    F: FnOnce() -> Option<Vec<u8>>,
+{
+    match cache_dir() {
+        Some(cache_dir) => compress_cached_in(&cache_dir, data, settings, extension, compress),
+        None => compress().map(|compressed| write_generated(&compressed, extension)),
+    }
+}
+
+fn compress_cached_in<F>(
+    cache_dir: &Path,
+    data: &[u8],
+    settings: &str,
+    extension: &str,
+    compress: F,
+) -> Option<GeneratedData>
+where
+    F: FnOnce() -> Option<Vec<u8>>,
+{
+    let mut hasher = Sha256::new();
+    // Newer versions could compress differently
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update([0]);
+    hasher.update(settings);
+    hasher.update([0]);
+    hasher.update(data);
+    let key = format!("{:x}", hasher.finalize());
+    // The entry holds the hash of the compressed data, which is stored in a
+    // file named after that hash. An empty entry means that the compressed
+    // data wasn't worth keeping.
+    let entry_path = cache_dir
+        .join(&key[..2])
+        .join(format!("{}.{}", key, extension));
+    let data_dir = cache_dir.join("data");
+    if let Ok(hash) = fs::read_to_string(&entry_path) {
+        if hash.is_empty() {
+            return None;
+        }
+        if let Some(path) = read_cached(&data_dir, &hash, extension) {
+            return Some(GeneratedData::File(path));
+        }
+    }
+    let compressed = match compress() {
+        Some(compressed) => compressed,
+        None => {
+            let _ = write_atomic(&entry_path, b"");
+            return None;
+        }
+    };
+    // The cache is only there to speed up builds, so if it can't be written
+    // the compressed data is embedded directly.
+    match write_content_addressed(&data_dir, &compressed, extension) {
+        Ok(path) => {
+            let hash = format!("{:x}", Sha256::digest(&compressed));
+            let _ = write_atomic(&entry_path, hash.as_bytes());
+            Some(GeneratedData::File(path))
+        }
+        Err(_) => Some(GeneratedData::Inline(compressed)),
+    }
+}
+
+/// Find the cached file with the data that has this hash. Returns `None` if
+/// the file is missing, or if its data doesn't match the hash.
+fn read_cached(data_dir: &Path, hash: &str, extension: &str) -> Option<String> {
+    if hash.len() != 64 || !hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let path = data_dir
+        .join(&hash[..2])
+        .join(format!("{}.{}", hash, extension));
+    let data = fs::read(&path).ok()?;
+    if format!("{:x}", Sha256::digest(&data)) != hash {
+        return None;
+    }
+    path.into_os_string().into_string().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn file_path(data: Option<GeneratedData>) -> String {
+        match data {
+            Some(GeneratedData::File(path)) => path,
+            other => panic!("expected a file, got {:?}", other),
+        }
+    }
+
+    fn test_cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "rust-embed-for-web-test-{}-{}",
+            name,
+            process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn compress_cached_reuses_earlier_results() {
+        let cache_dir = test_cache_dir("reuse");
+        let calls = Cell::new(0);
+        let compress = |output: Option<&[u8]>| {
+            calls.set(calls.get() + 1);
+            output.map(|v| v.to_vec())
+        };
+
+        // A miss compresses the data and caches it
+        let path = file_path(compress_cached_in(
+            &cache_dir,
+            b"data",
+            "level=1",
+            "gz",
+            || compress(Some(b"compressed")),
+        ));
+        assert_eq!(fs::read(&path).unwrap(), b"compressed");
+        assert_eq!(calls.get(), 1);
+
+        // A hit returns the same file without compressing again
+        let hit = compress_cached_in(&cache_dir, b"data", "level=1", "gz", || {
+            compress(Some(b"other"))
+        });
+        assert_eq!(hit, Some(GeneratedData::File(path.clone())));
+        assert_eq!(calls.get(), 1);
+
+        // Different settings compress the data again
+        let changed = file_path(compress_cached_in(
+            &cache_dir,
+            b"data",
+            "level=2",
+            "gz",
+            || compress(Some(b"changed")),
+        ));
+        assert_ne!(changed, path);
+        assert_eq!(fs::read(&changed).unwrap(), b"changed");
+        assert_eq!(calls.get(), 2);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn compress_cached_remembers_data_that_is_not_worth_keeping() {
+        let cache_dir = test_cache_dir("not-worth-keeping");
+        let calls = Cell::new(0);
+        for _ in 0..2 {
+            let path = compress_cached_in(&cache_dir, b"data", "level=1", "br", || {
+                calls.set(calls.get() + 1);
+                None
+            });
+            assert_eq!(path, None);
+        }
+        assert_eq!(calls.get(), 1);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn compress_cached_checks_the_cached_data() {
+        let cache_dir = test_cache_dir("tampered");
+        let path = file_path(compress_cached_in(
+            &cache_dir,
+            b"data",
+            "level=1",
+            "gz",
+            || Some(b"compressed".to_vec()),
+        ));
+        fs::write(&path, b"tampered!!").unwrap();
+
+        // The data no longer matches the hash in the cache, so it's compressed
+        // again
+        let path = file_path(compress_cached_in(
+            &cache_dir,
+            b"data",
+            "level=1",
+            "gz",
+            || Some(b"compressed".to_vec()),
+        ));
+        assert_eq!(fs::read(&path).unwrap(), b"compressed");
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn compress_cached_embeds_data_when_the_cache_is_not_writable() {
+        let cache_dir = test_cache_dir("not-writable");
+        // A file where the folder should be makes every write fail
+        fs::write(&cache_dir, b"").unwrap();
+        let data = compress_cached_in(&cache_dir, b"data", "level=1", "gz", || {
+            Some(b"compressed".to_vec())
+        });
+        assert_eq!(data, Some(GeneratedData::Inline(b"compressed".to_vec())));
+
+        fs::remove_file(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn content_addressed_files_are_checked() {
+        let dir = test_cache_dir("content-addressed");
+        let path = write_content_addressed(&dir, b"data", "gz").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"data");
+
+        // A file with different contents of the same length is replaced
+        fs::write(&path, b"evil").unwrap();
+        let rewritten = write_content_addressed(&dir, b"data", "gz").unwrap();
+        assert_eq!(rewritten, path);
+        assert_eq!(fs::read(&path).unwrap(), b"data");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+use std::{env, num::NonZeroUsize, panic, sync::Mutex, thread};
+
+/// The most threads that are used unless the number is set explicitly. Cargo
+/// already compiles several crates at the same time, each of which could have
+/// embeds, so using every CPU for each of them would run far too many threads.
+const DEFAULT_MAX_JOBS: usize = 4;
+
+/// The number of threads to prepare files with.
+///
+/// This is `RUST_EMBED_JOBS` if it is set, and otherwise the number of CPUs, up
+/// to `DEFAULT_MAX_JOBS`.
+fn jobs() -> usize {
+    if let Ok(value) = env::var("RUST_EMBED_JOBS") {
+        let jobs = value.parse::<NonZeroUsize>().unwrap_or_else(|_| {
+            panic!(
+                "#[derive(RustEmbed)] RUST_EMBED_JOBS must be a positive number, but it is {:?}",
+                value
+            )
+        });
+        return jobs.get();
+    }
+    thread::available_parallelism()
+        .map(|jobs| jobs.get().min(DEFAULT_MAX_JOBS))
+        .unwrap_or(1)
+}
+
+/// Map the items on several threads. The results are in the same order as
+/// the items, no matter which thread finishes first.
+///
+/// `f` can't create tokens, because the compiler only allows that on the
+/// thread that runs the macro.
+pub(crate) fn parallel_map<T, R, F>(items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    parallel_map_with(jobs(), items, f)
+}
+
+fn parallel_map_with<T, R, F>(jobs: usize, items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let jobs = jobs.min(items.len());
+    if jobs <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+    let len = items.len();
+    let items = Mutex::new(items.into_iter().enumerate());
+    let mut results: Vec<(usize, R)> = Vec::with_capacity(len);
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        // Take the lock only to get the next item, not while
+                        // running `f`
+                        let next = items.lock().unwrap().next();
+                        match next {
+                            Some((index, item)) => results.push((index, f(item))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            match worker.join() {
+                Ok(worker_results) => results.extend(worker_results),
+                // Show the original panic message as the compile error
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn results_are_in_the_order_of_the_items() {
+        let items: Vec<u64> = (0..64).collect();
+        let results = parallel_map_with(4, items, |item| {
+            // Earlier items take longer, so they finish after later ones
+            thread::sleep(Duration::from_millis(8 - item % 8));
+            (item, thread::current().id())
+        });
+        assert_eq!(
+            results.iter().map(|(item, _)| *item).collect::<Vec<_>>(),
+            (0..64).collect::<Vec<_>>()
+        );
+        let mut threads: Vec<_> = results.iter().map(|(_, id)| *id).collect();
+        threads.sort_by_key(|id| format!("{:?}", id));
+        threads.dedup();
+        assert!(threads.len() > 1);
+    }
+
+    #[test]
+    fn worker_panics_are_raised_again() {
+        let result = panic::catch_unwind(|| {
+            parallel_map_with(4, (0..16).collect(), |item: u32| {
+                if item == 5 {
+                    panic!("failed on {}", item);
+                }
+                item
+            })
+        });
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<String>().unwrap(), "failed on 5");
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use proc_macro2::TokenStream as TokenStream2;
+use rust_embed_for_web_utils::{Config, DynamicFile, EmbedableFile};
+
+use crate::folders::Folder;
+
+/// Finds the last modified times of the files that get embedded.
+pub(crate) struct LastModified {
+    enabled: bool,
+    /// The time of the last commit that changed each file, by canonical path.
+    git: Option<HashMap<PathBuf, i64>>,
+    source_date_epoch: Option<i64>,
+}
+
+impl LastModified {
+    pub(crate) fn new(config: &Config, folders: &[Folder]) -> Self {
+        let enabled = config.should_include_last_modified();
+        let git = if enabled && config.should_use_git_last_modified() {
+            Some(
+                folders
+                    .iter()
+                    // Crates published to crates.io aren't in a git
+                    // repository, and git may not be installed at all. The
+                    // files use their modification time then, like files that
+                    // were never committed.
+                    .flat_map(|folder| git_commit_times(&folder.path).unwrap_or_default())
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        LastModified {
+            enabled,
+            git,
+            source_date_epoch: read_source_date_epoch(),
+        }
+    }
+
+    /// The last modified time of a file. This is the time of the last commit
+    /// that changed the file if git is used, otherwise the modification time
+    /// of the file. Either way, it is clamped to `SOURCE_DATE_EPOCH` if set.
+    pub(crate) fn timestamp(&self, full_canonical_path: &str, file: &DynamicFile) -> Option<i64> {
+        if !self.enabled {
+            return None;
+        }
+        let timestamp = self
+            .git
+            .as_ref()
+            .and_then(|git| git.get(Path::new(full_canonical_path)).copied())
+            // Files that were never committed use the file system
+            .or_else(|| file.last_modified_timestamp())?;
+        Some(match self.source_date_epoch {
+            Some(source_date_epoch) => timestamp.min(source_date_epoch),
+            None => timestamp,
+        })
+    }
+}
+
+/// See https://reproducible-builds.org/specs/source-date-epoch/
+fn read_source_date_epoch() -> Option<i64> {
+    let value = env::var("SOURCE_DATE_EPOCH").ok()?;
+    match value.trim().parse() {
+        Ok(timestamp) => Some(timestamp),
+        Err(_) => panic!(
+            "#[derive(RustEmbed)] SOURCE_DATE_EPOCH must be a UNIX timestamp, got {:?}",
+            value
+        ),
+    }
+}
+
+fn run_git(folder_path: &str, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(folder_path)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(output.stdout)
+}
+
+/// Find the time of the last commit that changed each file in the folder.
+/// Returns `None` if the folder isn't in a git repository, or if git can't be
+/// run.
+fn git_commit_times(folder_path: &str) -> Option<HashMap<PathBuf, i64>> {
+    let toplevel = run_git(folder_path, &["rev-parse", "--show-toplevel"])?;
+    let toplevel = String::from_utf8_lossy(&toplevel);
+    let toplevel = std::fs::canonicalize(toplevel.trim()).ok()?;
+
+    // Commits are listed newest first, each one starting with \x01 and the
+    // commit time, followed by the files that it changed.
+    let log = run_git(
+        folder_path,
+        &[
+            "-c",
+            "log.showSignature=false",
+            "log",
+            "-z",
+            "--format=%x01%ct",
+            "--name-only",
+            "--",
+            ".",
+        ],
+    )?;
+    let mut times = HashMap::new();
+    let mut commit_time = None;
+    for part in log.split(|byte| *byte == 0) {
+        let part = String::from_utf8_lossy(part);
+        if let Some(time) = part.strip_prefix('\u{1}') {
+            commit_time = time.trim().parse::<i64>().ok();
+        } else if let Some(commit_time) = commit_time {
+            let name = part.trim_start_matches('\n');
+            if !name.is_empty() {
+                times.entry(toplevel.join(name)).or_insert(commit_time);
+            }
+        }
+    }
+    Some(times)
+}
+
+/// Generate code that drops the last modified time of dynamic files, if it is
+/// disabled. This expects a `file` variable, and shadows it.
+pub(crate) fn generate_dynamic_last_modified(config: &Config) -> TokenStream2 {
+    if config.should_include_last_modified() {
+        quote! {}
+    } else {
+        quote! {
+            let file = file.with_last_modified_timestamp(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process};
+
+    use super::*;
+
+    fn file_modified_at(timestamp: i64) -> (String, DynamicFile) {
+        let path = env::temp_dir().join(format!(
+            "rust-embed-for-web-last-modified-{}.txt",
+            process::id()
+        ));
+        fs::write(&path, "file").unwrap();
+        let file = DynamicFile::read_from_fs(&path)
+            .unwrap()
+            .with_last_modified_timestamp(Some(timestamp));
+        fs::remove_file(&path).unwrap();
+        (path.to_str().unwrap().to_owned(), file)
+    }
+
+    fn last_modified(
+        git: Option<HashMap<PathBuf, i64>>,
+        source_date_epoch: Option<i64>,
+    ) -> LastModified {
+        LastModified {
+            enabled: true,
+            git,
+            source_date_epoch,
+        }
+    }
+
+    #[test]
+    fn later_times_are_clamped_to_source_date_epoch() {
+        let (path, file) = file_modified_at(2000);
+        assert_eq!(
+            last_modified(None, None).timestamp(&path, &file),
+            Some(2000)
+        );
+        assert_eq!(
+            last_modified(None, Some(1000)).timestamp(&path, &file),
+            Some(1000)
+        );
+        assert_eq!(
+            last_modified(None, Some(3000)).timestamp(&path, &file),
+            Some(2000)
+        );
+    }
+
+    #[test]
+    fn git_times_are_clamped_to_source_date_epoch() {
+        let (path, file) = file_modified_at(2000);
+        let git: HashMap<PathBuf, i64> = vec![(PathBuf::from(&path), 1500)].into_iter().collect();
+        assert_eq!(
+            last_modified(Some(git.clone()), None).timestamp(&path, &file),
+            Some(1500)
+        );
+        assert_eq!(
+            last_modified(Some(git.clone()), Some(1000)).timestamp(&path, &file),
+            Some(1000)
+        );
+        // Files that were never committed use their modification time
+        assert_eq!(
+            last_modified(Some(HashMap::new()), Some(3000)).timestamp(&path, &file),
+            Some(2000)
+        );
+    }
+
+    #[test]
+    fn disabled_last_modified_is_none() {
+        let (path, file) = file_modified_at(2000);
+        let last_modified = LastModified {
+            enabled: false,
+            git: None,
+            source_date_epoch: Some(1000),
+        };
+        assert_eq!(last_modified.timestamp(&path, &file), None);
+    }
+}
@@ -5,6 +5,7 @@
 //! re-exports any necessary parts from this crate.
 #![recursion_limit = "1024"]
 #![forbid(unsafe_code)]
+#![cfg_attr(rust_embed_for_web_tracked_path, feature(proc_macro_tracked_path))]
 #[macro_use]
 extern crate quote;
 extern crate proc_macro;
@@ -13,13 +14,18 @@ mod attributes;
 mod compress;
 mod dynamic;
 mod embed;
+mod folders;
+mod generated;
+mod jobs;
+mod last_modified;
+mod lookup;
 
 use attributes::read_attribute_config;
 use dynamic::generate_dynamic_impl;
 use embed::generate_embed_impl;
+use folders::{file_exists, read_folders};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use std::{env, path::Path};
 use syn::{Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue};
 
 /// Find all pairs of the `name = "value"` attribute from the derive input
@@ -50,27 +56,6 @@ fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
         _ => panic!("RustEmbed can only be derived for unit structs"),
     };
 
-    let mut folder_paths = find_attribute_values(ast, "folder");
-    if folder_paths.len() != 1 {
-        panic!("#[derive(RustEmbed)] must contain one and only one folder attribute");
-    }
-    let folder_path = folder_paths.remove(0);
-    #[cfg(feature = "interpolate-folder-path")]
-    let folder_path = shellexpand::full(&folder_path).unwrap().to_string();
-
-    // Base relative paths on the Cargo.toml location
-    let folder_path = if Path::new(&folder_path).is_relative() {
-        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-            .join(folder_path)
-            .to_str()
-            .unwrap()
-            .to_owned()
-    } else {
-        folder_path
-    };
-
-    let config = read_attribute_config(ast);
-
     let prefixes = find_attribute_values(ast, "prefix");
     let prefix = if prefixes.is_empty() {
         "".to_string()
@@ -80,10 +65,22 @@ fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
         panic!("#[derive(RustEmbed)] must have at most one prefix, you supplied several");
     };
 
+    let folders = read_folders(ast, &prefix);
+
+    let config = read_attribute_config(ast);
+    if let Some(fallback) = config.get_fallback() {
+        if !file_exists(&folders, &format!("{}{}", prefix, fallback)) {
+            panic!(
+                "#[derive(RustEmbed)] fallback file {} does not exist in the folder",
+                fallback
+            );
+        }
+    }
+
     if cfg!(debug_assertions) && !cfg!(feature = "always-embed") {
-        generate_dynamic_impl(&ast.ident, &config, &folder_path, &prefix)
+        generate_dynamic_impl(&ast.ident, &config, &folders, &prefix)
     } else {
-        generate_embed_impl(&ast.ident, &config, &folder_path, &prefix)
+        generate_embed_impl(&ast.ident, &config, &folders, &prefix)
     }
 }
 
@@ -92,12 +89,31 @@ fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
     attributes(
         folder,
         prefix,
+        allow_overrides,
         include,
         exclude,
         gzip,
         br,
+        zstd,
+        gzip_level,
+        zopfli,
+        br_quality,
+        br_window,
+        zstd_level,
+        compression_min_ratio,
         preserve_source,
-        preserve_source_except
+        preserve_source_except,
+        fallback,
+        fallback_extensionless_only,
+        index_file,
+        override_env,
+        hashed_names,
+        integrity,
+        last_modified,
+        cache_control,
+        mime,
+        default_mime,
+        sniff_mime
     )
 )]
 /// A folder that is embedded into your program.
@@ -114,6 +130,18 @@ fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
 /// example will embed the files under `<your-workspace>/examples/public` into
 /// your program.
 ///
+/// You can add several folders, optionally with a prefix for each, and they
+/// will be combined. A file can only exist in one of them, unless you add
+/// `#[allow_overrides = true]`, in which case folders written later override
+/// the earlier ones.
+///
+/// ```ignore
+/// #[derive(RustEmbed)]
+/// #[folder = "frontend/dist"]
+/// #[folder("brand", prefix = "brand/")]
+/// struct MyEmbeddedFiles;
+/// ```
+///
 /// Please check the package readme for more details.
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream as TokenStream2;
+use rust_embed_for_web_utils::{hashed_path, Config};
+
+/// Find the paths of directories like `docs/` that resolve to the index file
+/// in them, for the embedded implementation.
+///
+/// Returns pairs of the directory path and the path of the index file.
+pub(crate) fn generate_index_aliases(config: &Config, paths: &[String]) -> Vec<(String, String)> {
+    let index_file = match config.get_index_file() {
+        Some(index_file) => index_file,
+        None => return vec![],
+    };
+    paths
+        .iter()
+        .filter_map(|path| {
+            let directory = path.strip_suffix(index_file)?;
+            if directory.is_empty() || directory.ends_with('/') {
+                Some((directory.to_string(), path.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Generate code that resolves directory paths like `docs/` to the index file
+/// in them, for the dynamic implementation. This shadows the `path` variable.
+pub(crate) fn generate_index_path(config: &Config) -> TokenStream2 {
+    match config.get_index_file() {
+        Some(index_file) => quote! {
+            let index_path;
+            let path = if path.is_empty() || path.ends_with('/') {
+                index_path = format!("{}{}", path, #index_file);
+                index_path.as_str()
+            } else {
+                path
+            };
+        },
+        None => quote! {},
+    }
+}
+
+/// Generate the `get_or_fallback` function, which is shared between the
+/// embedded and dynamic implementations.
+pub(crate) fn generate_get_or_fallback(
+    ident: &syn::Ident,
+    config: &Config,
+    prefix: &str,
+    file_type: &TokenStream2,
+) -> TokenStream2 {
+    let fallback = match config.get_fallback() {
+        Some(fallback) => format!("{}{}", prefix, fallback),
+        None => {
+            return quote! {
+                fn get_or_fallback(path: &str) -> Option<#file_type> {
+                    #ident::get(path)
+                }
+            }
+        }
+    };
+    let should_fallback = if config.is_fallback_extensionless_only() {
+        quote! {
+            !path.rsplit('/').next().unwrap_or_default().contains('.')
+        }
+    } else {
+        quote! { true }
+    };
+
+    quote! {
+        fn get_or_fallback(path: &str) -> Option<#file_type> {
+            #ident::get(path).or_else(|| {
+                if #should_fallback {
+                    #ident::get(#fallback)
+                } else {
+                    None
+                }
+            })
+        }
+    }
+}
+
+/// Generate the `url_for` and `manifest` functions for the embedded
+/// implementation, along with the hashed paths like `app.3fa9c1d2.js` that
+/// resolve to the file.
+///
+/// Returns pairs of the hashed path and the path of the file, and the
+/// functions.
+pub(crate) fn generate_hashed_names_embed(
+    ident: &syn::Ident,
+    config: &Config,
+    paths: &[String],
+    hashes: &[String],
+) -> (Vec<(String, String)>, TokenStream2) {
+    if !config.should_hash_names() {
+        return (vec![], quote! {});
+    }
+    let mut taken: HashSet<String> = paths.iter().cloned().collect();
+    let mut manifest_paths = vec![];
+    let mut hashed_paths = vec![];
+    for (path, hash) in paths.iter().zip(hashes) {
+        let hashed = hashed_path(path, hash);
+        // If a file already exists at the hashed path, then that file wins
+        if taken.insert(hashed.clone()) {
+            manifest_paths.push(path);
+            hashed_paths.push(hashed);
+        }
+    }
+    let aliases = hashed_paths
+        .iter()
+        .zip(&manifest_paths)
+        .map(|(hashed, path)| (hashed.clone(), path.to_string()))
+        .collect();
+    let manifest_len = manifest_paths.len();
+    // The paths are sorted, so the manifest is too
+    let functions = quote! {
+        fn hashed_names() -> &'static [(&'static str, &'static str)] {
+            static MANIFEST: [(&str, &str); #manifest_len] = [#((#manifest_paths, #hashed_paths)),*];
+            &MANIFEST
+        }
+
+        fn url_for(path: &str) -> Option<std::borrow::Cow<'static, str>> {
+            let manifest = #ident::hashed_names();
+            match manifest.binary_search_by(|(manifest_path, _)| (*manifest_path).cmp(path)) {
+                Ok(index) => Some(std::borrow::Cow::Borrowed(manifest[index].1)),
+                Err(_) => #ident::get(path).map(|_| std::borrow::Cow::Owned(path.to_string())),
+            }
+        }
+
+        fn manifest() -> rust_embed_for_web::Manifest {
+            rust_embed_for_web::Manifest::Embedded(#ident::hashed_names().iter())
+        }
+    };
+    (aliases, functions)
+}
+
+/// Generate the `get`, `url_for` and `manifest` functions for the dynamic
+/// implementation. `get` finds files by their hashed paths, then falls back to
+/// `get_unhashed`.
+pub(crate) fn generate_hashed_names_dynamic(ident: &syn::Ident, config: &Config) -> TokenStream2 {
+    if !config.should_hash_names() {
+        return quote! {};
+    }
+    quote! {
+        fn get(path: &str) -> Option<rust_embed_for_web::DynamicFile> {
+            #ident::get_unhashed(path).or_else(|| {
+                let unhashed = rust_embed_for_web::utils::unhashed_path(path)?;
+                let file = #ident::get_unhashed(&unhashed)?;
+                let hash = rust_embed_for_web::EmbedableFile::hash(&file);
+                // Only the current hash works, like in release builds
+                if rust_embed_for_web::utils::hashed_path(&unhashed, &hash) == path {
+                    Some(file)
+                } else {
+                    None
+                }
+            })
+        }
+
+        fn url_for(path: &str) -> Option<std::borrow::Cow<'static, str>> {
+            let file = #ident::get_unhashed(path)?;
+            let hash = rust_embed_for_web::EmbedableFile::hash(&file);
+            let url = rust_embed_for_web::utils::hashed_path(path, &hash);
+            Some(std::borrow::Cow::Owned(url))
+        }
+
+        fn manifest() -> rust_embed_for_web::Manifest {
+            let urls: Vec<(String, String)> = #ident::paths()
+                .filter_map(|path| {
+                    let url = #ident::url_for(&path)?;
+                    Some((path.into_owned(), url.into_owned()))
+                })
+                .collect();
+            rust_embed_for_web::Manifest::Dynamic(urls.into_iter())
+        }
+    }
+}
+
+/// Generate the `url_for` and `manifest` functions of the `RustEmbed` trait,
+/// which call the ones generated by `generate_hashed_names_embed` or
+/// `generate_hashed_names_dynamic`.
+pub(crate) fn generate_hashed_names_trait(ident: &syn::Ident, config: &Config) -> TokenStream2 {
+    if !config.should_hash_names() {
+        return quote! {};
+    }
+    quote! {
+        fn url_for(file_path: &str) -> Option<std::borrow::Cow<'static, str>> {
+          #ident::url_for(file_path)
+        }
+
+        fn manifest() -> rust_embed_for_web::Manifest {
+          #ident::manifest()
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use actix_web::{body::BoxBody, http::StatusCode, HttpRequest, HttpResponse, Responder};
+use rust_embed_for_web_utils::response::FileResponse;
+
+use crate::Lookup;
+
+impl<F: Responder<Body = BoxBody>> Responder for Lookup<F> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        match self {
+            Lookup::File(file) => file.respond_to(req),
+            Lookup::Redirect(_) => {
+                let redirect = FileResponse::redirect_to_directory(
+                    req.path(),
+                    Some(req.query_string()).filter(|v| !v.is_empty()),
+                );
+                let mut builder = HttpResponse::build(
+                    StatusCode::from_u16(redirect.status)
+                        .expect("Response status codes are always valid"),
+                );
+                for (name, value) in redirect.headers {
+                    builder.insert_header((name, value.as_ref()));
+                }
+                builder.finish()
+            }
+            Lookup::NotFound => HttpResponse::NotFound().finish(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    future::{ready, Ready},
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{Request, Response, StatusCode};
+use http_body_util::Full;
+use percent_encoding::percent_decode_str;
+use tower_service::Service;
+
+use rust_embed_for_web_utils::response::{respond, FileResponse, RequestHeaders};
+
+use crate::{Lookup, RustEmbed};
+
+/// A `tower` service that serves the files in an embed.
+///
+/// The path of the request is used to look up the file with
+/// `RustEmbed::lookup`, so index files and the fallback file are served if the
+/// embed has them. Directory paths missing the trailing slash are redirected.
+/// The service picks a compressed version of the file based on the
+/// `Accept-Encoding` header, answers conditional and range requests, and sets
+/// the `Content-Type`, `ETag` and `Last-Modified` headers.
+///
+/// With axum, you can serve the embed under some path with `nest_service`:
+///
+/// ```ignore
+/// #[derive(RustEmbed)]
+/// #[folder = "public/"]
+/// struct Assets;
+///
+/// let app = Router::new().nest_service("/static", ServeEmbed::<Assets>::new());
+/// ```
+pub struct ServeEmbed<E: RustEmbed> {
+    embed: PhantomData<fn() -> E>,
+}
+
+impl<E: RustEmbed> ServeEmbed<E> {
+    pub fn new() -> Self {
+        ServeEmbed { embed: PhantomData }
+    }
+}
+
+impl<E: RustEmbed> Default for ServeEmbed<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: RustEmbed> Clone for ServeEmbed<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: RustEmbed> Copy for ServeEmbed<E> {}
+
+fn into_response(file_response: FileResponse) -> Response<Full<Bytes>> {
+    let body = match file_response.body {
+        Cow::Borrowed(data) => Bytes::from_static(data),
+        Cow::Owned(data) => Bytes::from(data),
+    };
+    let mut builder = Response::builder().status(file_response.status);
+    for (name, value) in file_response.headers {
+        builder = builder.header(name, value.as_ref());
+    }
+    builder.body(Full::new(body)).unwrap_or_else(|_| {
+        let mut response = Response::new(Full::default());
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        response
+    })
+}
+
+impl<E: RustEmbed, B> Service<Request<B>> for ServeEmbed<E> {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let path = percent_decode_str(req.uri().path().trim_start_matches('/')).decode_utf8();
+        let lookup = match path {
+            Ok(path) => E::lookup(&path),
+            Err(_) => Lookup::NotFound,
+        };
+        let file_response = match lookup {
+            Lookup::File(file) => {
+                let headers = req.headers();
+                let request_headers =
+                    RequestHeaders::from_fn(|name| headers.get(name).and_then(|v| v.to_str().ok()));
+                respond(req.method().as_str(), &request_headers, &file)
+            }
+            Lookup::Redirect(_) => {
+                FileResponse::redirect_to_directory(req.uri().path(), req.uri().query())
+            }
+            Lookup::NotFound => FileResponse::not_found(),
+        };
+        ready(Ok(into_response(file_response)))
+    }
+}
@@ -7,10 +7,11 @@
 //! [example](https://github.com/SeriousBug/rust-embed-for-web/blob/master/examples/actix.rs)
 //! available too!
 //!
-//! If you are using this with Actix Web, there's an existing responder
-//! [`actix-web-rust-embed-responder`](https://lib.rs/crates/actix-web-rust-embed-responder)
-//! which will handle everything for you, from negotiating compressed responses
-//! to cache revalidation.
+//! If you are using this with Actix Web, enable the `actix` feature and you can
+//! return files directly from your handlers. If you are using axum or another
+//! `tower` based server, enable the `axum` feature and use `ServeEmbed`. Both
+//! will handle everything for you, from negotiating compressed responses to
+//! cache revalidation.
 #![forbid(unsafe_code)]
 
 #[allow(unused_imports)]
@@ -19,11 +20,25 @@ extern crate rust_embed_for_web_impl;
 
 pub use rust_embed_for_web_impl::*;
 
-pub use rust_embed_for_web_utils::{DynamicFile, EmbedableFile, EmbeddedFile, EmbeddedFileImpl};
+pub use rust_embed_for_web_utils::{
+    evaluate_conditional, evaluate_range, negotiate_encoding, ConditionalHeaders,
+    ConditionalResponse, DynamicFile, EmbedableFile, EmbeddedFile, EmbeddedFileImpl, Encoding,
+    IntegrityAlgorithm, NegotiatedBody, RangeResponse,
+};
 
 #[doc(hidden)]
 pub extern crate rust_embed_for_web_utils as utils;
 
+use std::{borrow::Cow, marker::PhantomData};
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+
+#[cfg(feature = "axum")]
+pub use crate::axum::ServeEmbed;
+
 /// A folder of embedded files.
 ///
 /// The type of the file `RustEmbed::File` depends on whether we're in debug
@@ -40,5 +55,165 @@ pub trait RustEmbed {
     type File: EmbedableFile;
 
     /// Get a file out of the folder.
+    ///
+    /// With the `#[index_file = "index.html"]` attribute, directory paths that
+    /// end with a slash like `docs/` (or the empty path for the root of the
+    /// folder) return the index file in that directory.
     fn get(file_path: &str) -> Option<Self::File>;
+
+    /// Iterate over the paths of all files in the folder.
+    ///
+    /// These are the same paths you would pass to `get`, so they include the
+    /// `prefix` and respect `include` and `exclude` attributes.
+    fn paths() -> Filenames;
+
+    /// Get a file out of the folder, or the fallback file if it doesn't exist.
+    ///
+    /// The fallback file is set with the `#[fallback = "index.html"]`
+    /// attribute, which is useful for single page apps. If there is no
+    /// fallback, this is the same as `get`.
+    fn get_or_fallback(file_path: &str) -> Option<Self::File> {
+        Self::get(file_path)
+    }
+
+    /// Look up a path the way a web server would.
+    ///
+    /// This returns the file at the path or the index file of a directory like
+    /// `get`, and tells you if you should redirect a directory path that is
+    /// missing the trailing slash, like `docs` to `docs/`. Otherwise it falls
+    /// back like `get_or_fallback`.
+    fn lookup(file_path: &str) -> Lookup<Self::File> {
+        if let Some(file) = Self::get(file_path) {
+            return Lookup::File(file);
+        }
+        if !file_path.is_empty() && !file_path.ends_with('/') {
+            let directory = format!("{}/", file_path);
+            if Self::get(&directory).is_some() {
+                return Lookup::Redirect(directory);
+            }
+        }
+        match Self::get_or_fallback(file_path) {
+            Some(file) => Lookup::File(file),
+            None => Lookup::NotFound,
+        }
+    }
+
+    /// Get the path to use in URLs for a file.
+    ///
+    /// With the `#[hashed_names = true]` attribute, this is the path with the
+    /// hash of the file in it, like `app.3fa9c1d2.js` for `app.js`. `get` will
+    /// return the file for that path too. Because the path changes whenever
+    /// the file does, you can cache these responses forever. Without the
+    /// attribute, this is the path itself.
+    ///
+    /// Returns `None` if the file doesn't exist.
+    fn url_for(file_path: &str) -> Option<Cow<'static, str>> {
+        Self::get(file_path).map(|_| Cow::from(file_path.to_string()))
+    }
+
+    /// Iterate over the paths of all files in the folder, along with the path
+    /// `url_for` returns for them.
+    fn manifest() -> Manifest {
+        let urls: Vec<(String, String)> = Self::paths()
+            .filter_map(|path| {
+                let url = Self::url_for(&path)?;
+                Some((path.into_owned(), url.into_owned()))
+            })
+            .collect();
+        Manifest::Dynamic(urls.into_iter())
+    }
+
+    /// Iterate over all files in the folder, along with their paths.
+    fn iter() -> Files<Self>
+    where
+        Self: Sized,
+    {
+        Files {
+            paths: Self::paths(),
+            embed: PhantomData,
+        }
+    }
+}
+
+/// The result of `RustEmbed::lookup`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lookup<F> {
+    /// The file that was found.
+    File(F),
+    /// The path is a directory with an index file, but the path is missing the
+    /// trailing slash. You should redirect to the path in here, so that
+    /// relative links inside the index file work.
+    Redirect(String),
+    /// There is no file at that path.
+    NotFound,
+}
+
+/// An iterator over the paths of the files in an embedded folder.
+///
+/// You can get one by calling `RustEmbed::paths`.
+pub enum Filenames {
+    /// Release builds, the paths are embedded into the program.
+    Embedded(std::slice::Iter<'static, &'static str>),
+    /// Debug builds, the paths are found by walking the folder.
+    Dynamic(std::vec::IntoIter<String>),
+}
+
+impl Iterator for Filenames {
+    type Item = Cow<'static, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Filenames::Embedded(names) => names.next().map(|v| Cow::from(*v)),
+            Filenames::Dynamic(names) => names.next().map(Cow::from),
+        }
+    }
+}
+
+/// An iterator over the paths of the files in an embedded folder, and the
+/// paths to use in URLs for them.
+///
+/// You can get one by calling `RustEmbed::manifest`.
+pub enum Manifest {
+    /// Release builds, the manifest is embedded into the program.
+    Embedded(std::slice::Iter<'static, (&'static str, &'static str)>),
+    /// Debug builds, or if file names are not hashed.
+    Dynamic(std::vec::IntoIter<(String, String)>),
+}
+
+impl Iterator for Manifest {
+    type Item = (Cow<'static, str>, Cow<'static, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Manifest::Embedded(urls) => urls
+                .next()
+                .map(|(path, url)| (Cow::from(*path), Cow::from(*url))),
+            Manifest::Dynamic(urls) => urls
+                .next()
+                .map(|(path, url)| (Cow::from(path), Cow::from(url))),
+        }
+    }
+}
+
+/// An iterator over the files in an embedded folder, and their paths.
+///
+/// You can get one by calling `RustEmbed::iter`.
+pub struct Files<E: RustEmbed> {
+    paths: Filenames,
+    embed: PhantomData<E>,
+}
+
+impl<E: RustEmbed> Iterator for Files<E> {
+    type Item = (Cow<'static, str>, E::File);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // In debug builds a file could get deleted after we list the folder,
+        // in which case we skip it.
+        for path in self.paths.by_ref() {
+            if let Some(file) = E::get(&path) {
+                return Some((path, file));
+            }
+        }
+        None
+    }
 }
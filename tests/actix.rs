@@ -0,0 +1,137 @@
+use actix_web::{
+    http::{header, StatusCode},
+    test, web, App, Responder,
+};
+use rust_embed_for_web::{EmbedableFile, EmbeddedFileImpl, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[index_file = "index.html"]
+struct Site;
+
+async fn serve(path: web::Path<String>) -> impl Responder {
+    Embed::get(&path)
+}
+
+async fn serve_lookup(path: web::Path<String>) -> impl Responder {
+    Site::lookup(&path)
+}
+
+async fn serve_impl(path: web::Path<String>) -> impl Responder {
+    Embed::get(&path).map(EmbeddedFileImpl::from)
+}
+
+macro_rules! call {
+    ($request:expr) => {{
+        let app = test::init_service(
+            App::new()
+                .route("/impl/{path:.*}", web::get().to(serve_impl))
+                .route("/site/{path:.*}", web::get().to(serve_lookup))
+                .route("/{path:.*}", web::to(serve)),
+        )
+        .await;
+        test::call_service(&app, $request.to_request()).await
+    }};
+}
+
+#[actix_web::test]
+async fn serves_files() {
+    let response = call!(test::TestRequest::get().uri("/index.html"));
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers().get(header::ETAG).unwrap(),
+        file.etag().as_ref()
+    );
+    let body = test::read_body(response).await;
+    assert_eq!(body, file.data().unwrap().as_ref());
+}
+
+#[actix_web::test]
+async fn serves_files_through_the_enum() {
+    let response = call!(test::TestRequest::get().uri("/impl/images/doc.txt"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = test::read_body(response).await;
+    assert_eq!(body, "Testing 1 2 3");
+}
+
+#[actix_web::test]
+async fn missing_files_are_not_found() {
+    let response = call!(test::TestRequest::get().uri("/does-not-exist"));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn matching_etag_is_not_modified() {
+    let etag = Embed::get("index.html").unwrap().etag().to_string();
+    let response = call!(test::TestRequest::get()
+        .uri("/index.html")
+        .insert_header((header::IF_NONE_MATCH, etag.as_str())));
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[actix_web::test]
+async fn head_is_answered() {
+    // Actix removes the body of HEAD responses when sending them, so we can't
+    // check that here.
+    let response = call!(test::TestRequest::default()
+        .method(actix_web::http::Method::HEAD)
+        .uri("/images/doc.txt"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::ETAG).is_some());
+}
+
+#[actix_web::test]
+async fn ranges_are_served() {
+    let response = call!(test::TestRequest::get()
+        .uri("/images/doc.txt")
+        .insert_header((header::RANGE, "bytes=8-")));
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers().get(header::CONTENT_RANGE).unwrap(),
+        "bytes 8-12/13"
+    );
+    let body = test::read_body(response).await;
+    assert_eq!(body, "1 2 3");
+}
+
+#[cfg(feature = "always-embed")]
+#[actix_web::test]
+async fn compressed_files_are_served() {
+    let response = call!(test::TestRequest::get()
+        .uri("/index.html")
+        .insert_header((header::ACCEPT_ENCODING, "br")));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_ENCODING).unwrap(),
+        "br"
+    );
+    let body = test::read_body(response).await;
+    assert_eq!(
+        body,
+        Embed::get("index.html")
+            .unwrap()
+            .data_br()
+            .unwrap()
+            .as_ref()
+    );
+}
+
+#[actix_web::test]
+async fn lookup_redirects_directories() {
+    let response = call!(test::TestRequest::get().uri("/site/docs"));
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers().get(header::LOCATION).unwrap(), "docs/");
+    let response = call!(test::TestRequest::get().uri("/site/docs/"));
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = call!(test::TestRequest::get().uri("/site/nope"));
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
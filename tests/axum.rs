@@ -0,0 +1,181 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use rust_embed_for_web::{EmbedableFile, RustEmbed, ServeEmbed};
+use tower::ServiceExt;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[cache_control("*.html", "no-cache")]
+struct Embed;
+
+fn app() -> Router {
+    Router::new().nest_service("/static", ServeEmbed::<Embed>::new())
+}
+
+async fn get(request: Request<Body>) -> (StatusCode, header::HeaderMap, Vec<u8>) {
+    let response = app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, body.to_vec())
+}
+
+fn request(path: &str) -> axum::http::request::Builder {
+    Request::builder().uri(path)
+}
+
+#[tokio::test]
+async fn serves_files() {
+    let (status, headers, body) =
+        get(request("/static/index.html").body(Body::empty()).unwrap()).await;
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "text/html; charset=utf-8");
+    assert_eq!(headers[header::ETAG], file.etag().as_ref());
+    assert_eq!(headers[header::CACHE_CONTROL], "no-cache");
+    assert_eq!(
+        headers[header::CONTENT_LENGTH],
+        file.data().unwrap().len().to_string()
+    );
+    assert!(headers.get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(body, file.data().unwrap().as_ref());
+}
+
+#[tokio::test]
+async fn serves_files_in_folders() {
+    let (status, headers, body) = get(request("/static/images/doc.txt")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get(header::CACHE_CONTROL).is_none());
+    assert_eq!(body, b"Testing 1 2 3");
+}
+
+#[tokio::test]
+async fn missing_files_are_not_found() {
+    let (status, _, _) = get(request("/static/does-not-exist")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn matching_etag_is_not_modified() {
+    let etag = Embed::get("index.html").unwrap().etag().to_string();
+    let (status, headers, body) = get(request("/static/index.html")
+        .header(header::IF_NONE_MATCH, etag.as_str())
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers[header::ETAG], etag.as_str());
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn head_has_no_body() {
+    let (status, headers, body) = get(request("/static/images/doc.txt")
+        .method("HEAD")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_LENGTH], "13");
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn other_methods_are_not_allowed() {
+    let (status, headers, _) = get(request("/static/index.html")
+        .method("POST")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(headers[header::ALLOW], "GET, HEAD");
+}
+
+#[tokio::test]
+async fn ranges_are_served() {
+    let (status, headers, body) = get(request("/static/images/doc.txt")
+        .header(header::RANGE, "bytes=0-6")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers[header::CONTENT_RANGE], "bytes 0-6/13");
+    assert_eq!(body, b"Testing");
+}
+
+#[cfg(feature = "always-embed")]
+#[tokio::test]
+async fn compressed_files_are_served() {
+    let (status, headers, body) = get(request("/static/index.html")
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .body(Body::empty())
+        .unwrap())
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+    assert_eq!(headers[header::VARY], "Accept-Encoding");
+    assert_eq!(
+        body,
+        Embed::get("index.html")
+            .unwrap()
+            .data_gzip()
+            .unwrap()
+            .as_ref()
+    );
+}
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[fallback = "index.html"]
+struct Spa;
+
+#[tokio::test]
+async fn fallback_is_served() {
+    let app = Router::new().nest_service("/app", ServeEmbed::<Spa>::new());
+    let response = app
+        .oneshot(request("/app/some/route").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+}
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[index_file = "index.html"]
+struct Site;
+
+#[tokio::test]
+async fn directories_are_redirected() {
+    let app = Router::new().nest_service("/site", ServeEmbed::<Site>::new());
+    let response = app
+        .clone()
+        .oneshot(request("/site/docs?a=b").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(response.headers()[header::LOCATION], "docs/?a=b");
+
+    let response = app
+        .oneshot(request("/site/docs/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+}
@@ -0,0 +1,75 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[cache_control("*.html", "no-cache")]
+#[cache_control("images/**", "public, max-age=31536000, immutable")]
+#[cache_control("images/*.txt", "no-store")]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+#[cache_control("static/*.css", "public, max-age=3600")]
+struct Prefixed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct NoCacheControl;
+
+#[test]
+fn cache_control_matches_globs() {
+    assert_eq!(
+        Embed::get("index.html").unwrap().cache_control().unwrap(),
+        "no-cache"
+    );
+    assert_eq!(
+        Embed::get("docs/index.html")
+            .unwrap()
+            .cache_control()
+            .unwrap(),
+        "no-cache"
+    );
+    assert_eq!(
+        Embed::get("images/llama.png")
+            .unwrap()
+            .cache_control()
+            .unwrap(),
+        "public, max-age=31536000, immutable"
+    );
+}
+
+#[test]
+fn first_matching_pattern_wins() {
+    assert_eq!(
+        Embed::get("images/doc.txt")
+            .unwrap()
+            .cache_control()
+            .unwrap(),
+        "public, max-age=31536000, immutable"
+    );
+}
+
+#[test]
+fn no_matching_pattern() {
+    assert!(Embed::get("main.css").unwrap().cache_control().is_none());
+    assert!(NoCacheControl::get("index.html")
+        .unwrap()
+        .cache_control()
+        .is_none());
+}
+
+#[test]
+fn patterns_include_the_prefix() {
+    assert_eq!(
+        Prefixed::get("static/main.css")
+            .unwrap()
+            .cache_control()
+            .unwrap(),
+        "public, max-age=3600"
+    );
+    assert!(Prefixed::get("static/index.html")
+        .unwrap()
+        .cache_control()
+        .is_none());
+}
@@ -0,0 +1,58 @@
+use std::io::{BufReader, Write};
+
+use flate2::write::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 9]
+#[br_quality = 11]
+#[br_window = 24]
+struct Best;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 1]
+#[br_quality = 0]
+#[br_window = 10]
+struct Fast;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 0]
+struct Uncompressed;
+
+#[test]
+fn better_levels_are_not_larger() {
+    let best = Best::get("index.html").unwrap();
+    let fast = Fast::get("index.html").unwrap();
+    assert!(best.data_gzip().unwrap().len() <= fast.data_gzip().unwrap().len());
+    assert!(best.data_br().unwrap().len() <= fast.data_br().unwrap().len());
+}
+
+#[test]
+fn gzip_level_zero_is_not_embedded() {
+    // Level 0 only stores the data, so it's larger than the original
+    let file = Uncompressed::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+}
+
+#[test]
+fn compression_levels_roundtrip() {
+    for file in [
+        Best::get("index.html").unwrap(),
+        Fast::get("index.html").unwrap(),
+    ] {
+        let mut decompressed: Vec<u8> = Vec::new();
+        let mut decoder = GzDecoder::new(&mut decompressed);
+        decoder.write_all(&file.data_gzip().unwrap()[..]).unwrap();
+        decoder.finish().unwrap();
+        assert_eq!(decompressed, file.data().unwrap().as_ref());
+
+        let mut decompressed: Vec<u8> = Vec::new();
+        let compressed = file.data_br().unwrap();
+        let mut data_read = BufReader::new(&compressed[..]);
+        brotli::BrotliDecompress(&mut data_read, &mut decompressed).unwrap();
+        assert_eq!(decompressed, file.data().unwrap().as_ref());
+    }
+}
@@ -7,6 +7,11 @@ use rust_embed_for_web::{EmbedableFile, RustEmbed};
 #[folder = "examples/public"]
 struct Embed;
 
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compression_min_ratio = 0.01]
+struct StrictRatio;
+
 #[test]
 fn html_files_are_compressed() {
     assert!(Embed::get("index.html").unwrap().data_gzip().is_some());
@@ -22,6 +27,22 @@ fn image_files_are_not_compressed() {
     assert!(Embed::get("images/flower.jpg").unwrap().data_br().is_none());
 }
 
+#[test]
+fn files_that_grow_when_compressed_are_not_compressed() {
+    // This file is so small that compression overhead makes it larger
+    assert!(Embed::get("images/doc.txt").unwrap().data_gzip().is_none());
+    assert!(Embed::get("images/doc.txt").unwrap().data_br().is_none());
+}
+
+#[test]
+fn compression_ratio_can_be_configured() {
+    assert!(StrictRatio::get("index.html")
+        .unwrap()
+        .data_gzip()
+        .is_none());
+    assert!(StrictRatio::get("index.html").unwrap().data_br().is_none());
+}
+
 #[test]
 fn compression_gzip_roundtrip() {
     let compressed = Embed::get("index.html").unwrap().data_gzip().unwrap();
@@ -42,3 +63,40 @@ fn compression_br_roundtrip() {
     let decompressed_body = String::from_utf8_lossy(&decompressed[..]);
     assert!(decompressed_body.starts_with("<!DOCTYPE html>"));
 }
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[preserve_source = false]
+struct NoSource;
+
+#[test]
+fn compressed_lengths_match_the_data() {
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(file.gzip_len(), file.data_gzip().map(|v| v.len()));
+    assert_eq!(file.br_len(), file.data_br().map(|v| v.len()));
+    assert!(file.gzip_len().unwrap() < file.len());
+
+    let file = Embed::get("images/flower.jpg").unwrap();
+    assert_eq!(file.gzip_len(), None);
+    assert_eq!(file.br_len(), None);
+}
+
+#[test]
+fn length_is_known_without_the_source() {
+    let file = NoSource::get("index.html").unwrap();
+    assert!(file.data().is_none());
+    assert_eq!(file.len(), Embed::get("index.html").unwrap().len());
+}
+
+#[test]
+fn source_is_kept_when_nothing_is_compressed() {
+    let file = NoSource::get("images/flower.jpg").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    assert_eq!(file.data().map(|v| v.len()), Some(file.len()));
+
+    let file = NoSource::get("images/doc.txt").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    assert_eq!(file.data(), Embed::get("images/doc.txt").unwrap().data());
+}
@@ -0,0 +1,185 @@
+use chrono::{TimeZone, Utc};
+use rust_embed_for_web::{
+    evaluate_conditional, ConditionalHeaders, ConditionalResponse, EmbedableFile, RustEmbed,
+};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+fn evaluate(method: &str, headers: ConditionalHeaders) -> ConditionalResponse {
+    let file = Embed::get("index.html").unwrap();
+    evaluate_conditional(method, &headers, &file)
+}
+
+fn etag() -> String {
+    Embed::get("index.html").unwrap().etag().to_string()
+}
+
+fn http_date(offset: i64) -> String {
+    date_with_format(offset, "%a, %d %b %Y %H:%M:%S GMT")
+}
+
+fn date_with_format(offset: i64, format: &str) -> String {
+    let timestamp = Embed::get("index.html")
+        .unwrap()
+        .last_modified_timestamp()
+        .unwrap();
+    Utc.timestamp_opt(timestamp + offset, 0)
+        .unwrap()
+        .format(format)
+        .to_string()
+}
+
+#[test]
+fn no_conditions_is_ok() {
+    assert_eq!(
+        evaluate("GET", ConditionalHeaders::default()),
+        ConditionalResponse::Ok
+    );
+}
+
+#[test]
+fn if_none_match_with_matching_etag_is_not_modified() {
+    let etag = etag();
+    let headers = ConditionalHeaders {
+        if_none_match: Some(&etag),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::NotModified);
+    assert_eq!(evaluate("HEAD", headers), ConditionalResponse::NotModified);
+    assert_eq!(
+        evaluate("POST", headers),
+        ConditionalResponse::PreconditionFailed
+    );
+}
+
+#[test]
+fn if_none_match_uses_weak_comparison() {
+    let header = format!("\"other\", W/{}", etag());
+    let headers = ConditionalHeaders {
+        if_none_match: Some(&header),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::NotModified);
+}
+
+#[test]
+fn if_none_match_with_other_etag_is_ok() {
+    let headers = ConditionalHeaders {
+        if_none_match: Some("\"foo\", \"bar,baz\""),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::Ok);
+}
+
+#[test]
+fn if_none_match_star_is_not_modified() {
+    let headers = ConditionalHeaders {
+        if_none_match: Some("*"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::NotModified);
+}
+
+#[test]
+fn if_match_uses_strong_comparison() {
+    let etag = etag();
+    let weak = format!("W/{}", etag);
+    let matching = ConditionalHeaders {
+        if_match: Some(&etag),
+        ..Default::default()
+    };
+    let weak_matching = ConditionalHeaders {
+        if_match: Some(&weak),
+        ..Default::default()
+    };
+    let star = ConditionalHeaders {
+        if_match: Some("*"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", matching), ConditionalResponse::Ok);
+    assert_eq!(evaluate("GET", star), ConditionalResponse::Ok);
+    assert_eq!(
+        evaluate("GET", weak_matching),
+        ConditionalResponse::PreconditionFailed
+    );
+}
+
+#[test]
+fn if_modified_since() {
+    let same = http_date(0);
+    let later = http_date(60);
+    let earlier = http_date(-60);
+    for (date, expected) in [
+        (&same, ConditionalResponse::NotModified),
+        (&later, ConditionalResponse::NotModified),
+        (&earlier, ConditionalResponse::Ok),
+    ] {
+        let headers = ConditionalHeaders {
+            if_modified_since: Some(date),
+            ..Default::default()
+        };
+        assert_eq!(evaluate("GET", headers), expected);
+    }
+}
+
+#[test]
+fn if_none_match_has_precedence_over_if_modified_since() {
+    let later = http_date(60);
+    let headers = ConditionalHeaders {
+        if_none_match: Some("\"other\""),
+        if_modified_since: Some(&later),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::Ok);
+}
+
+#[test]
+fn if_unmodified_since() {
+    let earlier = http_date(-60);
+    let later = http_date(60);
+    let failing = ConditionalHeaders {
+        if_unmodified_since: Some(&earlier),
+        ..Default::default()
+    };
+    let passing = ConditionalHeaders {
+        if_unmodified_since: Some(&later),
+        ..Default::default()
+    };
+    assert_eq!(
+        evaluate("GET", failing),
+        ConditionalResponse::PreconditionFailed
+    );
+    assert_eq!(evaluate("GET", passing), ConditionalResponse::Ok);
+}
+
+#[test]
+fn obsolete_date_formats_are_accepted() {
+    let rfc850 = date_with_format(0, "%A, %d-%b-%y %H:%M:%S GMT");
+    let asctime = date_with_format(0, "%a %b %e %H:%M:%S %Y");
+    for date in [&rfc850, &asctime] {
+        let headers = ConditionalHeaders {
+            if_modified_since: Some(date),
+            ..Default::default()
+        };
+        assert_eq!(evaluate("GET", headers), ConditionalResponse::NotModified);
+    }
+}
+
+#[test]
+fn invalid_dates_are_ignored() {
+    let headers = ConditionalHeaders {
+        if_modified_since: Some("yesterday"),
+        if_unmodified_since: Some("tomorrow"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate("GET", headers), ConditionalResponse::Ok);
+}
+
+#[test]
+fn status_codes() {
+    assert_eq!(ConditionalResponse::Ok.status_code(), 200);
+    assert_eq!(ConditionalResponse::NotModified.status_code(), 304);
+    assert_eq!(ConditionalResponse::PreconditionFailed.status_code(), 412);
+}
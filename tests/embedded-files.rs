@@ -0,0 +1,73 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[index_file = "index.html"]
+#[hashed_names = true]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[exclude = "*"]
+struct Empty;
+
+#[test]
+fn embedded_files_are_sorted_by_path() {
+    let paths: Vec<&str> = Embed::embedded_files()
+        .iter()
+        .map(|(path, _)| *path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "docs/index.html",
+            "images/doc.txt",
+            "images/flower.jpg",
+            "images/llama.png",
+            "index.html",
+            "main.css",
+        ]
+    );
+}
+
+#[test]
+fn paths_are_sorted() {
+    let paths: Vec<String> = Embed::paths().map(|v| v.to_string()).collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+}
+
+#[test]
+fn embedded_files_match_get() {
+    for (path, file) in Embed::embedded_files() {
+        assert_eq!(file.name(), Embed::get(path).unwrap().name());
+        assert_eq!(file.hash(), Embed::get(path).unwrap().hash());
+    }
+}
+
+#[test]
+fn aliases_resolve_to_files() {
+    let index = Embed::get("index.html").unwrap();
+    assert_eq!(Embed::get("").unwrap(), index);
+    let docs = Embed::get("docs/index.html").unwrap();
+    assert_eq!(Embed::get("docs/").unwrap(), docs);
+    let url = Embed::url_for("main.css").unwrap();
+    assert_eq!(Embed::get(&url).unwrap(), Embed::get("main.css").unwrap());
+}
+
+#[test]
+fn missing_paths_are_not_found() {
+    assert!(Embed::get("aaa").is_none());
+    assert!(Embed::get("docs").is_none());
+    assert!(Embed::get("zzz").is_none());
+    assert!(Embed::get("index.htm").is_none());
+    assert!(Embed::get("images/").is_none());
+}
+
+#[test]
+fn empty_folders_have_no_files() {
+    assert!(Empty::embedded_files().is_empty());
+    assert!(Empty::get("").is_none());
+    assert!(Empty::get("index.html").is_none());
+}
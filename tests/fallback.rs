@@ -0,0 +1,73 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[fallback = "index.html"]
+struct Fallback;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[fallback = "index.html"]
+#[fallback_extensionless_only = true]
+struct ExtensionlessFallback;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "app/"]
+#[fallback = "index.html"]
+struct PrefixedFallback;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct NoFallback;
+
+#[test]
+fn existing_files_are_returned() {
+    let file = Fallback::get_or_fallback("images/doc.txt").unwrap();
+    assert_eq!(file.name(), "doc.txt");
+}
+
+#[test]
+fn missing_files_fall_back() {
+    let file = Fallback::get_or_fallback("some/route").unwrap();
+    assert_eq!(file.name(), "index.html");
+    let file = Fallback::get_or_fallback("missing.js").unwrap();
+    assert_eq!(file.name(), "index.html");
+}
+
+#[test]
+fn get_does_not_fall_back() {
+    assert!(Fallback::get("some/route").is_none());
+}
+
+#[test]
+fn extensionless_only_fallback() {
+    let file = ExtensionlessFallback::get_or_fallback("some/route").unwrap();
+    assert_eq!(file.name(), "index.html");
+    let file = ExtensionlessFallback::get_or_fallback("some/route/").unwrap();
+    assert_eq!(file.name(), "index.html");
+    assert!(ExtensionlessFallback::get_or_fallback("missing.js").is_none());
+    assert!(ExtensionlessFallback::get_or_fallback("some.dir/missing.js").is_none());
+}
+
+#[test]
+fn fallback_includes_the_prefix() {
+    let file = PrefixedFallback::get_or_fallback("app/some/route").unwrap();
+    assert_eq!(file.name(), "index.html");
+}
+
+#[test]
+fn no_fallback_is_the_same_as_get() {
+    assert!(NoFallback::get_or_fallback("some/route").is_none());
+    assert!(NoFallback::get_or_fallback("index.html").is_some());
+}
+
+fn get_with_trait<T: RustEmbed>(path: &str) -> Option<T::File> {
+    T::get_or_fallback(path)
+}
+
+#[test]
+fn using_trait_also_works() {
+    let file = get_with_trait::<Fallback>("some/route").unwrap();
+    assert_eq!(file.name(), "index.html");
+}
@@ -0,0 +1,100 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+mod common;
+use common::read_embed_as_string;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[folder("examples/brand", prefix = "brand/")]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[folder = "examples/brand"]
+#[allow_overrides = true]
+struct Overridden;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[folder("examples/brand", prefix = "brand/")]
+#[prefix = "static/"]
+struct Prefixed;
+
+fn sorted_paths<T: RustEmbed>() -> Vec<String> {
+    let mut paths: Vec<String> = T::paths().map(|v| v.to_string()).collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn files_from_all_folders_are_there() {
+    assert!(Embed::get("index.html").is_some());
+    assert!(Embed::get("brand/logo.txt").is_some());
+    assert!(Embed::get("logo.txt").is_none());
+    assert_eq!(
+        read_embed_as_string(Embed::get("brand/main.css").unwrap()),
+        "body { color: rebeccapurple; }\n"
+    );
+}
+
+#[test]
+fn paths_lists_files_from_all_folders() {
+    assert_eq!(
+        sorted_paths::<Embed>(),
+        vec![
+            "brand/logo.txt",
+            "brand/main.css",
+            "docs/index.html",
+            "images/doc.txt",
+            "images/flower.jpg",
+            "images/llama.png",
+            "index.html",
+            "main.css",
+        ]
+    );
+}
+
+#[test]
+fn later_folder_overrides() {
+    assert_eq!(
+        read_embed_as_string(Overridden::get("main.css").unwrap()),
+        "body { color: rebeccapurple; }\n"
+    );
+    assert!(Overridden::get("index.html").is_some());
+    assert!(Overridden::get("logo.txt").is_some());
+}
+
+#[test]
+fn overridden_paths_are_listed_once() {
+    assert_eq!(
+        sorted_paths::<Overridden>(),
+        vec![
+            "docs/index.html",
+            "images/doc.txt",
+            "images/flower.jpg",
+            "images/llama.png",
+            "index.html",
+            "logo.txt",
+            "main.css",
+        ]
+    );
+}
+
+#[test]
+fn prefixes_are_combined() {
+    assert!(Prefixed::get("static/index.html").is_some());
+    assert!(Prefixed::get("static/brand/logo.txt").is_some());
+    assert!(Prefixed::get("brand/logo.txt").is_none());
+}
+
+#[test]
+fn iter_uses_the_overridden_file() {
+    let (_, file) = Overridden::iter()
+        .find(|(path, _)| path == "main.css")
+        .unwrap();
+    assert_eq!(file.name(), "main.css");
+    assert_eq!(
+        read_embed_as_string(file),
+        "body { color: rebeccapurple; }\n"
+    );
+}
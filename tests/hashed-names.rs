@@ -0,0 +1,96 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[hashed_names = true]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+#[hashed_names = true]
+struct Prefixed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct NotHashed;
+
+fn is_short_hash(value: &str) -> bool {
+    value.len() == 8 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[test]
+fn url_for_has_the_hash() {
+    let url = Embed::url_for("main.css").unwrap();
+    let hash = url
+        .strip_prefix("main.")
+        .and_then(|v| v.strip_suffix(".css"))
+        .unwrap();
+    assert!(is_short_hash(hash), "{}", url);
+
+    let url = Embed::url_for("images/llama.png").unwrap();
+    let hash = url
+        .strip_prefix("images/llama.")
+        .and_then(|v| v.strip_suffix(".png"))
+        .unwrap();
+    assert!(is_short_hash(hash), "{}", url);
+}
+
+#[test]
+fn url_for_missing_file() {
+    assert!(Embed::url_for("missing.css").is_none());
+}
+
+#[test]
+fn hashed_path_returns_the_file() {
+    let url = Embed::url_for("images/llama.png").unwrap();
+    let file = Embed::get(&url).unwrap();
+    assert_eq!(file.hash(), Embed::get("images/llama.png").unwrap().hash());
+    assert_eq!(file.name(), "llama.png");
+}
+
+#[test]
+fn unhashed_path_still_works() {
+    assert!(Embed::get("main.css").is_some());
+}
+
+#[test]
+fn wrong_hash_is_rejected() {
+    assert!(Embed::get("main.00000000.css").is_none());
+    assert!(Embed::get("missing.00000000.css").is_none());
+}
+
+#[test]
+fn prefix_is_kept() {
+    let url = Prefixed::url_for("static/main.css").unwrap();
+    assert!(url.starts_with("static/main."));
+    assert!(Prefixed::get(&url).is_some());
+    assert!(Prefixed::url_for("main.css").is_none());
+}
+
+#[test]
+fn manifest_has_all_files() {
+    let mut manifest: Vec<(String, String)> = Embed::manifest()
+        .map(|(path, url)| (path.into_owned(), url.into_owned()))
+        .collect();
+    manifest.sort();
+    let mut paths: Vec<String> = Embed::paths().map(|v| v.into_owned()).collect();
+    paths.sort();
+    assert_eq!(
+        manifest.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        paths.iter().collect::<Vec<_>>()
+    );
+    for (path, url) in manifest {
+        assert_ne!(path, url);
+        assert_eq!(Embed::url_for(&path).unwrap(), url);
+        assert!(Embed::get(&url).is_some());
+    }
+}
+
+#[test]
+fn not_hashed_url_for_is_the_path() {
+    assert_eq!(NotHashed::url_for("main.css").unwrap(), "main.css");
+    assert!(NotHashed::url_for("missing.css").is_none());
+    assert!(NotHashed::manifest().all(|(path, url)| path == url));
+    assert!(NotHashed::get(&Embed::url_for("main.css").unwrap()).is_none());
+}
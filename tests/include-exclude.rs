@@ -21,3 +21,18 @@ fn excluded_file_is_missing() {
 fn included_overrides_the_exclude() {
     assert!(Embed::get("images/flower.jpg").is_some());
 }
+
+#[test]
+fn paths_respect_include_and_exclude() {
+    let mut paths: Vec<String> = Embed::paths().map(|v| v.to_string()).collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            "docs/index.html",
+            "images/flower.jpg",
+            "index.html",
+            "main.css"
+        ]
+    );
+}
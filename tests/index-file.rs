@@ -0,0 +1,82 @@
+use rust_embed_for_web::{EmbedableFile, Lookup, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[index_file = "index.html"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+#[index_file = "index.html"]
+struct Prefixed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct NoIndex;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[index_file = "index.html"]
+#[fallback = "main.css"]
+struct WithFallback;
+
+fn hash<F: EmbedableFile>(file: F) -> String {
+    file.hash().to_string()
+}
+
+#[test]
+fn directories_resolve_to_index() {
+    assert_eq!(
+        hash(Embed::get("docs/").unwrap()),
+        hash(Embed::get("docs/index.html").unwrap())
+    );
+    assert_eq!(
+        hash(Embed::get("").unwrap()),
+        hash(Embed::get("index.html").unwrap())
+    );
+}
+
+#[test]
+fn directories_without_slash_are_missing() {
+    assert!(Embed::get("docs").is_none());
+    assert!(Embed::get("images/").is_none());
+}
+
+#[test]
+fn index_includes_the_prefix() {
+    assert_eq!(
+        hash(Prefixed::get("static/docs/").unwrap()),
+        hash(Prefixed::get("static/docs/index.html").unwrap())
+    );
+    assert!(Prefixed::get("static/").is_some());
+    assert!(Prefixed::get("").is_none());
+}
+
+#[test]
+fn index_is_opt_in() {
+    assert!(NoIndex::get("docs/").is_none());
+    assert!(NoIndex::get("").is_none());
+}
+
+#[test]
+fn lookup_redirects_directories() {
+    assert_eq!(Embed::lookup("docs"), Lookup::Redirect("docs/".to_string()));
+    assert!(matches!(Embed::lookup("docs/"), Lookup::File(_)));
+    assert!(matches!(Embed::lookup("index.html"), Lookup::File(_)));
+    assert_eq!(Embed::lookup("images"), Lookup::NotFound);
+    assert_eq!(Embed::lookup("does-not-exist"), Lookup::NotFound);
+    assert_eq!(NoIndex::lookup("docs"), Lookup::NotFound);
+}
+
+#[test]
+fn lookup_falls_back() {
+    assert_eq!(
+        WithFallback::lookup("docs"),
+        Lookup::Redirect("docs/".to_string())
+    );
+    match WithFallback::lookup("some/route") {
+        Lookup::File(file) => assert_eq!(file.name(), "main.css"),
+        other => panic!("Expected the fallback file, got {:?}", other),
+    }
+}
@@ -0,0 +1,62 @@
+use rust_embed_for_web::{DynamicFile, EmbedableFile, IntegrityAlgorithm, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[integrity = "sha384"]
+struct Sha384;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[integrity = "sha512"]
+struct Sha512;
+
+#[test]
+fn integrity_defaults_to_sha256() {
+    assert_eq!(
+        Embed::get("images/doc.txt").unwrap().integrity(),
+        "sha256-eZ3sqyRuvpJL2bPyjEBO86ZUvNzj6YyFVciNedWwxSc="
+    );
+}
+
+#[test]
+fn integrity_with_sha384() {
+    assert_eq!(
+        Sha384::get("images/doc.txt").unwrap().integrity(),
+        "sha384-uKE2GHAlScjk7zVxUEeztYU8bn+3HBTO8xKE6zNypv8Bmk4+E21CJcg157FmfrFK"
+    );
+}
+
+#[test]
+fn integrity_with_sha512() {
+    assert_eq!(
+        Sha512::get("images/doc.txt").unwrap().integrity(),
+        "sha512-vgqNZouVJB9xJsANnRs88BAbluRN0Z+YF39MvsT79GeuzhwEFba6BPDomf4mEyw4gln4X8GjBf6B2o9k1YdcuA=="
+    );
+}
+
+#[test]
+fn integrity_of_dynamic_file() {
+    let file = DynamicFile::read_from_fs("examples/public/images/doc.txt").unwrap();
+    assert_eq!(
+        file.integrity(),
+        "sha256-eZ3sqyRuvpJL2bPyjEBO86ZUvNzj6YyFVciNedWwxSc="
+    );
+    let file = file.with_integrity_algorithm(IntegrityAlgorithm::Sha384);
+    assert_eq!(
+        file.integrity(),
+        "sha384-uKE2GHAlScjk7zVxUEeztYU8bn+3HBTO8xKE6zNypv8Bmk4+E21CJcg157FmfrFK"
+    );
+}
+
+#[test]
+fn integrity_is_the_same_for_all_files_types() {
+    let file = DynamicFile::read_from_fs("examples/public/main.css").unwrap();
+    assert_eq!(
+        Embed::get("main.css").unwrap().integrity(),
+        file.integrity()
+    );
+}
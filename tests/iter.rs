@@ -0,0 +1,66 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+struct Prefixed;
+
+fn sorted_paths<T: RustEmbed>() -> Vec<String> {
+    let mut paths: Vec<String> = T::paths().map(|v| v.to_string()).collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn paths_lists_all_files() {
+    assert_eq!(
+        sorted_paths::<Embed>(),
+        vec![
+            "docs/index.html",
+            "images/doc.txt",
+            "images/flower.jpg",
+            "images/llama.png",
+            "index.html",
+            "main.css",
+        ]
+    );
+}
+
+#[test]
+fn paths_include_the_prefix() {
+    assert_eq!(
+        sorted_paths::<Prefixed>(),
+        vec![
+            "static/docs/index.html",
+            "static/images/doc.txt",
+            "static/images/flower.jpg",
+            "static/images/llama.png",
+            "static/index.html",
+            "static/main.css",
+        ]
+    );
+}
+
+#[test]
+fn every_path_can_be_fetched() {
+    for path in Prefixed::paths() {
+        assert!(Prefixed::get(&path).is_some());
+    }
+}
+
+#[test]
+fn iter_returns_files_with_paths() {
+    let mut files: Vec<(String, String)> = Embed::iter()
+        .map(|(path, file)| (path.to_string(), file.name().to_string()))
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), 6);
+    assert_eq!(
+        files[1],
+        ("images/doc.txt".to_string(), "doc.txt".to_string())
+    );
+}
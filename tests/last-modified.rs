@@ -0,0 +1,47 @@
+use std::process::Command;
+
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[last_modified = false]
+struct NoLastModified;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[last_modified = "git"]
+struct GitLastModified;
+
+fn last_commit_time(path: &str) -> i64 {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct", "--", path])
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn last_modified_can_be_disabled() {
+    let file = NoLastModified::get("index.html").unwrap();
+    assert_eq!(file.last_modified(), None);
+    assert_eq!(file.last_modified_timestamp(), None);
+}
+
+#[test]
+fn last_modified_from_git() {
+    let file = GitLastModified::get("images/doc.txt").unwrap();
+    assert_eq!(
+        file.last_modified_timestamp(),
+        Some(last_commit_time("examples/public/images/doc.txt"))
+    );
+    let file = GitLastModified::get("docs/index.html").unwrap();
+    assert_eq!(
+        file.last_modified_timestamp(),
+        Some(last_commit_time("examples/public/docs/index.html"))
+    );
+    assert!(file.last_modified().is_some());
+}
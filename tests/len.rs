@@ -0,0 +1,24 @@
+use rust_embed_for_web::{DynamicFile, EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[test]
+fn len_is_the_length_of_the_data() {
+    let file = Embed::get("images/doc.txt").unwrap();
+    assert_eq!(file.len(), 13);
+    assert!(!file.is_empty());
+
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(file.len(), file.data().unwrap().len());
+}
+
+#[test]
+fn dynamic_files_are_not_compressed() {
+    let file = DynamicFile::read_from_fs("examples/public/index.html").unwrap();
+    assert_eq!(file.len(), file.data().unwrap().len());
+    assert_eq!(file.gzip_len(), None);
+    assert_eq!(file.br_len(), None);
+    assert_eq!(file.zstd_len(), None);
+}
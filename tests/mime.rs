@@ -0,0 +1,86 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/mime"]
+#[mime("*.foo", "application/x-foo")]
+#[default_mime = "application/octet-stream"]
+#[sniff_mime = true]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/mime"]
+struct NoSniff;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[mime("*.css", "text/css; charset=iso-8859-1")]
+struct Public;
+
+fn mime_type<T: RustEmbed>(path: &str) -> Option<String> {
+    T::get(path).unwrap().mime_type().map(|v| v.into_owned())
+}
+
+#[test]
+fn mime_override() {
+    assert_eq!(
+        mime_type::<Embed>("module.foo").unwrap(),
+        "application/x-foo"
+    );
+}
+
+#[test]
+fn mime_override_keeps_parameters() {
+    assert_eq!(
+        mime_type::<Public>("main.css").unwrap(),
+        "text/css; charset=iso-8859-1"
+    );
+}
+
+#[test]
+fn mime_from_extension() {
+    assert_eq!(
+        mime_type::<Embed>("app.webmanifest").unwrap(),
+        "application/manifest+json; charset=utf-8"
+    );
+    assert_eq!(
+        mime_type::<Public>("images/llama.png").unwrap(),
+        "image/png"
+    );
+}
+
+#[test]
+fn charset_for_text() {
+    assert_eq!(
+        mime_type::<Public>("index.html").unwrap(),
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(
+        mime_type::<Public>("images/doc.txt").unwrap(),
+        "text/plain; charset=utf-8"
+    );
+}
+
+#[test]
+fn sniffed_mime() {
+    assert_eq!(mime_type::<Embed>("logo").unwrap(), "image/png");
+    assert_eq!(
+        mime_type::<Embed>("LICENSE").unwrap(),
+        "text/plain; charset=utf-8"
+    );
+}
+
+#[test]
+fn default_mime() {
+    assert_eq!(
+        mime_type::<Embed>("binary.qqq").unwrap(),
+        "application/octet-stream"
+    );
+}
+
+#[test]
+fn unknown_mime_without_attributes() {
+    assert_eq!(mime_type::<NoSniff>("logo"), None);
+    assert_eq!(mime_type::<NoSniff>("LICENSE"), None);
+    assert_eq!(mime_type::<NoSniff>("binary.qqq"), None);
+    assert_eq!(mime_type::<NoSniff>("module.foo"), None);
+}
@@ -0,0 +1,125 @@
+use rust_embed_for_web::{negotiate_encoding, EmbedableFile, Encoding, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[preserve_source = false]
+struct NoSource;
+
+fn encoding_for(accept_encoding: Option<&str>) -> Option<Encoding> {
+    let file = Embed::get("index.html").unwrap();
+    negotiate_encoding(accept_encoding, &file).map(|body| body.encoding)
+}
+
+#[test]
+fn no_header_gets_identity() {
+    assert_eq!(encoding_for(None), Some(Encoding::Identity));
+}
+
+#[test]
+fn empty_header_gets_identity() {
+    assert_eq!(encoding_for(Some("")), Some(Encoding::Identity));
+}
+
+#[test]
+fn br_is_preferred() {
+    assert_eq!(encoding_for(Some("gzip, deflate, br")), Some(Encoding::Br));
+}
+
+#[test]
+fn quality_values_are_respected() {
+    assert_eq!(
+        encoding_for(Some("br;q=0.5, gzip;q=0.8")),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(
+        encoding_for(Some("br;q=0, gzip;q=0")),
+        Some(Encoding::Identity)
+    );
+    assert_eq!(
+        encoding_for(Some("gzip;q=0.5, identity;q=0.9")),
+        Some(Encoding::Identity)
+    );
+}
+
+#[test]
+fn malformed_quality_values_are_ignored() {
+    for header in [
+        "*;q=nan",
+        "*;q=NaN, gzip;q=inf",
+        "br;q=-1",
+        "br;q=2",
+        "br;q=1.5",
+        "br;q=0.1234",
+        "br;q=.5",
+        "br;q=1e0",
+        "br;q=",
+    ] {
+        assert_eq!(
+            encoding_for(Some(header)),
+            Some(Encoding::Identity),
+            "{}",
+            header
+        );
+    }
+    assert_eq!(
+        encoding_for(Some("br;q=nan, gzip;q=0.5")),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(encoding_for(Some("br;q=1.000")), Some(Encoding::Br));
+    assert_eq!(encoding_for(Some("br;q=0.")), Some(Encoding::Identity));
+}
+
+#[test]
+fn names_are_case_insensitive() {
+    assert_eq!(encoding_for(Some("GZIP")), Some(Encoding::Gzip));
+    assert_eq!(encoding_for(Some("x-gzip")), Some(Encoding::Gzip));
+}
+
+#[test]
+fn wildcard_matches_unlisted_encodings() {
+    assert_eq!(encoding_for(Some("*")), Some(Encoding::Br));
+    assert_eq!(encoding_for(Some("br;q=0, *")), Some(Encoding::Gzip));
+}
+
+#[test]
+fn identity_can_be_refused() {
+    let file = Embed::get("images/flower.jpg").unwrap();
+    assert!(negotiate_encoding(Some("gzip, identity;q=0"), &file).is_none());
+    assert!(negotiate_encoding(Some("*;q=0"), &file).is_none());
+    assert_eq!(
+        negotiate_encoding(Some("*;q=0, identity"), &file)
+            .unwrap()
+            .encoding,
+        Encoding::Identity
+    );
+}
+
+#[test]
+fn uncompressed_files_are_served_as_identity() {
+    let file = Embed::get("images/flower.jpg").unwrap();
+    let body = negotiate_encoding(Some("gzip, br"), &file).unwrap();
+    assert_eq!(body.encoding, Encoding::Identity);
+    assert_eq!(body.encoding.content_encoding(), None);
+    assert_eq!(body.data, file.data().unwrap());
+}
+
+#[test]
+fn compressed_data_is_returned() {
+    let file = Embed::get("index.html").unwrap();
+    let body = negotiate_encoding(Some("gzip"), &file).unwrap();
+    assert_eq!(body.encoding.content_encoding(), Some("gzip"));
+    assert_eq!(body.data, file.data_gzip().unwrap());
+}
+
+#[test]
+fn missing_source_is_decompressed() {
+    let file = NoSource::get("index.html").unwrap();
+    assert!(file.data().is_none());
+    let body = negotiate_encoding(Some("identity"), &file).unwrap();
+    assert_eq!(body.encoding, Encoding::Identity);
+    assert_eq!(body.data, Embed::get("index.html").unwrap().data().unwrap());
+}
@@ -0,0 +1,97 @@
+use std::{fs, path::PathBuf};
+
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+mod common;
+use common::read_embed_as_string;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[override_env = "RUST_EMBED_TEST_OVERRIDE_DIR"]
+#[index_file = "index.html"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[override_env = "RUST_EMBED_TEST_OVERRIDE_DIR_UNSET"]
+struct NotOverridden;
+
+fn set_up_override_dir() {
+    let folder = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("override-env");
+    let _ = fs::remove_dir_all(&folder);
+    fs::create_dir_all(folder.join("docs")).unwrap();
+    fs::write(folder.join("main.css"), "body { color: red; }").unwrap();
+    fs::write(folder.join("docs/index.html"), "Overridden docs").unwrap();
+    fs::write(folder.join("new.txt"), "Not embedded").unwrap();
+    std::env::set_var("RUST_EMBED_TEST_OVERRIDE_DIR", &folder);
+}
+
+#[test]
+fn override_dir_is_used() {
+    set_up_override_dir();
+
+    let css = Embed::get("main.css").unwrap();
+    assert_eq!(read_embed_as_string(css), "body { color: red; }");
+    let docs = Embed::get("docs/").unwrap();
+    assert_eq!(read_embed_as_string(docs), "Overridden docs");
+
+    // Files that aren't overridden are still embedded
+    let index = Embed::get("index.html").unwrap();
+    assert!(read_embed_as_string(index).starts_with("<!DOCTYPE html>"));
+    assert!(Embed::get("images/llama.png").is_some());
+
+    // Only embedded files can be overridden
+    assert!(Embed::get("new.txt").is_none());
+    assert!(Embed::get("../override-env/main.css").is_none());
+}
+
+#[test]
+fn embedded_files_without_override_dir() {
+    let css = NotOverridden::get("main.css").unwrap();
+    assert_eq!(
+        read_embed_as_string(css),
+        read_embed_as_string(
+            rust_embed_for_web::DynamicFile::read_from_fs("examples/public/main.css").unwrap()
+        )
+    );
+    assert!(NotOverridden::get("main.css")
+        .unwrap()
+        .data_gzip()
+        .is_some());
+}
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[override_env = "RUST_EMBED_TEST_OVERRIDE_DIR_HASHED"]
+#[hashed_names = true]
+struct Hashed;
+
+#[test]
+fn hashed_names_are_overridden() {
+    let folder = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("override-env-hashed");
+    let _ = fs::remove_dir_all(&folder);
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("main.css"), "body { color: blue; }").unwrap();
+    std::env::set_var("RUST_EMBED_TEST_OVERRIDE_DIR_HASHED", &folder);
+
+    // The URL is still the one of the embedded file
+    let url = Hashed::url_for("main.css").unwrap();
+    assert_ne!(url, "main.css");
+    assert!(!folder.join(url.as_ref()).exists());
+
+    let css = Hashed::get(&url).unwrap();
+    assert_eq!(read_embed_as_string(css), "body { color: blue; }");
+    let css = Hashed::get("main.css").unwrap();
+    assert_eq!(read_embed_as_string(css), "body { color: blue; }");
+}
+
+fn get_file_with_trait<T: RustEmbed>(path: &str) -> Option<T::File> {
+    T::get(path)
+}
+
+#[test]
+fn trait_returns_the_file_impl() {
+    let file: Option<rust_embed_for_web::EmbeddedFileImpl> =
+        get_file_with_trait::<NotOverridden>("index.html");
+    assert!(file.is_some());
+}
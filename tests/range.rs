@@ -0,0 +1,165 @@
+use rust_embed_for_web::{evaluate_range, EmbedableFile, RangeResponse, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+// The contents of this file are "Testing 1 2 3"
+const PATH: &str = "images/doc.txt";
+
+fn range(range: Option<&str>, if_range: Option<&str>) -> RangeResponse {
+    let file = Embed::get(PATH).unwrap();
+    evaluate_range(range, if_range, &file)
+}
+
+fn assert_partial(response: RangeResponse, expected_data: &str, expected_range: &str) {
+    match response {
+        RangeResponse::Partial {
+            data,
+            content_range,
+        } => {
+            assert_eq!(std::str::from_utf8(&data).unwrap(), expected_data);
+            assert_eq!(content_range, expected_range);
+        }
+        other => panic!("Expected a partial response, got {:?}", other),
+    }
+}
+
+#[test]
+fn no_range_is_full() {
+    assert!(matches!(range(None, None), RangeResponse::Full));
+}
+
+#[test]
+fn single_ranges() {
+    assert_partial(range(Some("bytes=0-6"), None), "Testing", "bytes 0-6/13");
+    assert_partial(range(Some("bytes=8-"), None), "1 2 3", "bytes 8-12/13");
+    assert_partial(range(Some("bytes=-3"), None), "2 3", "bytes 10-12/13");
+    assert_partial(range(Some("bytes=10-100"), None), "2 3", "bytes 10-12/13");
+    assert_partial(
+        range(Some("bytes=-100"), None),
+        "Testing 1 2 3",
+        "bytes 0-12/13",
+    );
+}
+
+#[test]
+fn invalid_ranges_are_ignored() {
+    for header in [
+        "bytes=5-2",
+        "bytes=a-b",
+        "items=0-5",
+        "bytes 0-5",
+        "bytes=0-1,x",
+    ] {
+        assert!(matches!(range(Some(header), None), RangeResponse::Full));
+    }
+}
+
+#[test]
+fn unsatisfiable_ranges() {
+    for header in ["bytes=13-", "bytes=20-30", "bytes=-0"] {
+        match range(Some(header), None) {
+            RangeResponse::NotSatisfiable { content_range } => {
+                assert_eq!(content_range, "bytes */13")
+            }
+            other => panic!("Expected range not satisfiable, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn unsatisfiable_ranges_are_skipped_among_others() {
+    assert_partial(
+        range(Some("bytes=50-60, 0-6"), None),
+        "Testing",
+        "bytes 0-6/13",
+    );
+}
+
+#[test]
+fn multiple_ranges() {
+    match range(Some("bytes=0-6, -1"), None) {
+        RangeResponse::Multipart { data, content_type } => {
+            let boundary = content_type
+                .strip_prefix("multipart/byteranges; boundary=")
+                .unwrap();
+            let expected = format!(
+                "--{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 0-6/13\r\n\r\nTesting\r\n\
+                 --{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 12-12/13\r\n\r\n3\r\n\
+                 --{b}--\r\n",
+                b = boundary
+            );
+            assert_eq!(std::str::from_utf8(&data).unwrap(), expected);
+        }
+        other => panic!("Expected a multipart response, got {:?}", other),
+    }
+}
+
+#[test]
+fn overlapping_and_adjacent_ranges_are_merged() {
+    assert_partial(
+        range(Some("bytes=0-3, 2-6"), None),
+        "Testing",
+        "bytes 0-6/13",
+    );
+    assert_partial(
+        range(Some("bytes=5-6, 0-1, 2-4"), None),
+        "Testing",
+        "bytes 0-6/13",
+    );
+    match range(Some("bytes=10-12, 4-6, 0-3"), None) {
+        RangeResponse::Multipart { data, .. } => {
+            let data = String::from_utf8(data).unwrap();
+            assert_eq!(data.matches("Content-Range").count(), 2);
+            let first = data.find("bytes 0-6/13").unwrap();
+            let second = data.find("bytes 10-12/13").unwrap();
+            assert!(first < second);
+        }
+        other => panic!("Expected a multipart response, got {:?}", other),
+    }
+}
+
+#[test]
+fn ranges_larger_than_the_file_get_the_full_file() {
+    let header = format!("bytes={}", vec!["0-"; 32].join(","));
+    assert!(matches!(range(Some(&header), None), RangeResponse::Full));
+    assert!(matches!(
+        range(Some("bytes=0-9, 5-12"), None),
+        RangeResponse::Full
+    ));
+}
+
+#[test]
+fn if_range_with_etag() {
+    let etag = Embed::get(PATH).unwrap().etag().to_string();
+    let weak = format!("W/{}", etag);
+    assert_partial(
+        range(Some("bytes=0-6"), Some(&etag)),
+        "Testing",
+        "bytes 0-6/13",
+    );
+    assert!(matches!(
+        range(Some("bytes=0-6"), Some("\"other\"")),
+        RangeResponse::Full
+    ));
+    assert!(matches!(
+        range(Some("bytes=0-6"), Some(&weak)),
+        RangeResponse::Full
+    ));
+}
+
+#[test]
+fn if_range_with_date() {
+    let file = Embed::get(PATH).unwrap();
+    let last_modified = file.last_modified().unwrap();
+    assert_partial(
+        range(Some("bytes=0-6"), Some(&last_modified)),
+        "Testing",
+        "bytes 0-6/13",
+    );
+    assert!(matches!(
+        range(Some("bytes=0-6"), Some("Thu, 01 Jan 1970 00:00:00 GMT")),
+        RangeResponse::Full
+    ));
+}
@@ -0,0 +1,82 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[test]
+fn existing_file_is_still_there() {
+    assert!(Embed::get("index.html").is_some());
+    assert!(Embed::get("images/doc.txt").is_some());
+}
+
+#[test]
+fn parent_directory_is_rejected() {
+    assert!(Embed::get("../actix.rs").is_none());
+    assert!(Embed::get("../../Cargo.toml").is_none());
+    assert!(Embed::get("images/../../actix.rs").is_none());
+    assert!(Embed::get("..\\actix.rs").is_none());
+}
+
+#[test]
+fn parent_directory_inside_the_folder_is_rejected() {
+    assert!(Embed::get("images/../index.html").is_none());
+}
+
+#[test]
+fn absolute_path_is_rejected() {
+    let cargo_toml = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    assert!(Embed::get(cargo_toml).is_none());
+    assert!(Embed::get("/etc/passwd").is_none());
+    assert!(Embed::get("/index.html").is_none());
+    assert!(Embed::get("\\index.html").is_none());
+}
+
+#[test]
+fn paths_are_not_normalized() {
+    assert!(Embed::get("./index.html").is_none());
+    assert!(Embed::get("images//llama.png").is_none());
+    assert!(Embed::get("images/./llama.png").is_none());
+}
+
+#[test]
+fn nul_byte_is_rejected() {
+    assert!(Embed::get("index.html\0").is_none());
+    assert!(Embed::get("index.html\0.png").is_none());
+}
+
+fn get_file_with_trait<T: RustEmbed>(path: &str) -> Option<T::File> {
+    T::get(path)
+}
+
+#[test]
+fn using_trait_also_rejects() {
+    assert!(get_file_with_trait::<Embed>("../actix.rs").is_none());
+}
+
+#[cfg(all(unix, debug_assertions, not(feature = "always-embed")))]
+mod symlinks {
+    use std::{fs, os::unix::fs::symlink, path::PathBuf};
+
+    use rust_embed_for_web::RustEmbed;
+
+    // The folder is populated by the test below, at runtime
+    #[derive(RustEmbed)]
+    #[folder = "$CARGO_TARGET_TMPDIR/traversal/public"]
+    struct Linked;
+
+    #[test]
+    fn symlink_out_of_the_folder_is_rejected() {
+        let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("traversal");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("public")).unwrap();
+        fs::write(root.join("secret.txt"), "secret").unwrap();
+        fs::write(root.join("public/inside.txt"), "inside").unwrap();
+        symlink(root.join("secret.txt"), root.join("public/outside.txt")).unwrap();
+        symlink("inside.txt", root.join("public/link.txt")).unwrap();
+
+        assert!(Linked::get("inside.txt").is_some());
+        assert!(Linked::get("link.txt").is_some());
+        assert!(Linked::get("outside.txt").is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use std::io::Write;
+
+use flate2::write::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct DefaultGzip;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[zopfli = true]
+struct Zopfli;
+
+#[test]
+fn zopfli_is_not_larger_than_default() {
+    let zopfli = Zopfli::get("index.html").unwrap().data_gzip().unwrap();
+    let default = DefaultGzip::get("index.html").unwrap().data_gzip().unwrap();
+    assert!(zopfli.len() <= default.len());
+}
+
+#[test]
+fn compression_zopfli_roundtrip() {
+    let compressed = Zopfli::get("index.html").unwrap().data_gzip().unwrap();
+    let mut decompressed: Vec<u8> = Vec::new();
+    let mut decoder = GzDecoder::new(&mut decompressed);
+    decoder.write_all(&compressed[..]).unwrap();
+    decoder.finish().unwrap();
+    let decompressed_body = String::from_utf8_lossy(&decompressed[..]);
+    assert!(decompressed_body.starts_with("<!DOCTYPE html>"));
+}
@@ -0,0 +1,37 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+struct DefaultZstd;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+#[zstd = true]
+struct TrueZstd;
+
+#[test]
+fn zstd_is_not_used_by_default() {
+    let file = DefaultZstd::get("index.html").unwrap();
+    assert!(file.data_zstd().is_none());
+}
+
+#[test]
+fn zstd_is_used_when_enabled() {
+    let file = TrueZstd::get("index.html").unwrap();
+    assert!(file.data_zstd().is_some());
+    assert_eq!(file.zstd_len(), file.data_zstd().map(|v| v.len()));
+}
+
+#[test]
+fn image_files_dont_get_zstd_compressed() {
+    let file = TrueZstd::get("images/flower.jpg").unwrap();
+    assert!(file.data_zstd().is_none());
+}
+
+#[test]
+fn compression_zstd_roundtrip() {
+    let compressed = TrueZstd::get("index.html").unwrap().data_zstd().unwrap();
+    let decompressed = zstd::decode_all(&compressed[..]).unwrap();
+    let decompressed_body = String::from_utf8_lossy(&decompressed[..]);
+    assert!(decompressed_body.starts_with("<!DOCTYPE html>"));
+}
@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use actix_web::{body::BoxBody, http::Method, web::Bytes, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    response::{respond, RequestHeaders},
+    DynamicFile, EmbedableFile, EmbeddedFile, EmbeddedFileImpl,
+};
+
+fn respond_to<F: EmbedableFile>(file: &F, req: &HttpRequest) -> HttpResponse {
+    let headers = req.headers();
+    let request_headers =
+        RequestHeaders::from_fn(|name| headers.get(name).and_then(|v| v.to_str().ok()));
+    // Actix skips the body of responses to HEAD requests by itself, and it
+    // needs the body to set the `Content-Length` header correctly.
+    let method = if req.method() == Method::HEAD {
+        "GET"
+    } else {
+        req.method().as_str()
+    };
+    let file_response = respond(method, &request_headers, file);
+
+    let mut builder = HttpResponse::build(
+        actix_web::http::StatusCode::from_u16(file_response.status)
+            .expect("Response status codes are always valid"),
+    );
+    for (name, value) in file_response.headers {
+        // Actix sets the length from the body
+        if name != "Content-Length" {
+            builder.insert_header((name, value.as_ref()));
+        }
+    }
+    match file_response.body {
+        Cow::Borrowed(data) => builder.body(Bytes::from_static(data)),
+        Cow::Owned(data) => builder.body(data),
+    }
+}
+
+impl Responder for EmbeddedFile {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        respond_to(&self, req)
+    }
+}
+
+impl Responder for DynamicFile {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        respond_to(&self, req)
+    }
+}
+
+impl Responder for EmbeddedFileImpl {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        respond_to(&self, req)
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::EmbedableFile;
+
+/// The conditional headers of a request.
+///
+/// Fill in the values of the headers that are present in the request, and
+/// leave the rest as `None`. If the request has several of the same header,
+/// join them with commas.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConditionalHeaders<'t> {
+    pub if_match: Option<&'t str>,
+    pub if_none_match: Option<&'t str>,
+    pub if_modified_since: Option<&'t str>,
+    pub if_unmodified_since: Option<&'t str>,
+}
+
+/// How a conditional request should be answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionalResponse {
+    /// All preconditions passed, respond with the file as usual.
+    Ok,
+    /// The client already has the file, respond with `304 Not Modified` and
+    /// no body.
+    NotModified,
+    /// A precondition failed, respond with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+impl ConditionalResponse {
+    /// The HTTP status code for this response.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ConditionalResponse::Ok => 200,
+            ConditionalResponse::NotModified => 304,
+            ConditionalResponse::PreconditionFailed => 412,
+        }
+    }
+}
+
+/// A single entity tag, parsed out of a header.
+pub(crate) struct EntityTag<'t> {
+    weak: bool,
+    /// The opaque tag, including the quotes.
+    tag: &'t str,
+}
+
+/// The parsed value of an `If-Match` or `If-None-Match` header.
+pub(crate) enum EntityTagList<'t> {
+    Any,
+    Tags(Vec<EntityTag<'t>>),
+}
+
+impl<'t> EntityTagList<'t> {
+    pub(crate) fn parse(header: &'t str) -> Self {
+        if header.trim() == "*" {
+            return EntityTagList::Any;
+        }
+        let mut tags = Vec::new();
+        let mut rest = header;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+            if rest.is_empty() {
+                break;
+            }
+            let weak = rest.starts_with("W/");
+            if weak {
+                rest = &rest[2..];
+            }
+            // The tag is always quoted, and can contain commas so we can't
+            // just split the header on them.
+            let end = match rest.strip_prefix('"').and_then(|v| v.find('"')) {
+                Some(end) => end + 2,
+                // Malformed header, ignore the rest of it
+                None => break,
+            };
+            tags.push(EntityTag {
+                weak,
+                tag: &rest[..end],
+            });
+            rest = &rest[end..];
+        }
+        EntityTagList::Tags(tags)
+    }
+
+    /// Check if any of the tags matches the ETag of the file. The ETags of
+    /// embedded files are always strong.
+    pub(crate) fn matches(&self, etag: &str, strong_comparison: bool) -> bool {
+        match self {
+            EntityTagList::Any => true,
+            EntityTagList::Tags(tags) => tags
+                .iter()
+                .any(|tag| tag.tag == etag && !(strong_comparison && tag.weak)),
+        }
+    }
+}
+
+/// Parse an HTTP date into a UNIX timestamp. All 3 formats allowed by RFC 9110
+/// are accepted.
+pub(crate) fn parse_http_date(date: &str) -> Option<i64> {
+    let date = date.trim();
+    DateTime::parse_from_rfc2822(date)
+        .map(|v| v.timestamp())
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(date, "%A, %d-%b-%y %H:%M:%S GMT")
+                .map(|v| v.and_utc().timestamp())
+        })
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(date, "%a %b %e %H:%M:%S %Y")
+                .map(|v| v.and_utc().timestamp())
+        })
+        .ok()
+}
+
+/// Decide how to answer a conditional request for a file.
+///
+/// This follows the precedence rules of RFC 9110 section 13.2.2: `If-Match`
+/// is checked first, then `If-Unmodified-Since` if there was no `If-Match`,
+/// then `If-None-Match`, and finally `If-Modified-Since` if there was no
+/// `If-None-Match`. Dates that can't be parsed are ignored, as are dates for
+/// files that don't have a last modified time.
+///
+/// The `method` is the HTTP method of the request. `If-None-Match` and
+/// `If-Modified-Since` can only produce `NotModified` for `GET` and `HEAD`
+/// requests.
+///
+/// ```
+/// # use rust_embed_for_web_utils::{evaluate_conditional, ConditionalHeaders, ConditionalResponse, EmbedableFile};
+/// fn respond<F: EmbedableFile>(if_none_match: Option<&str>, file: &F) {
+///     let headers = ConditionalHeaders {
+///         if_none_match,
+///         ..Default::default()
+///     };
+///     match evaluate_conditional("GET", &headers, file) {
+///         ConditionalResponse::Ok => println!("200 OK"),
+///         ConditionalResponse::NotModified => println!("304 Not Modified"),
+///         ConditionalResponse::PreconditionFailed => println!("412 Precondition Failed"),
+///     }
+/// }
+/// ```
+pub fn evaluate_conditional<F: EmbedableFile>(
+    method: &str,
+    headers: &ConditionalHeaders,
+    file: &F,
+) -> ConditionalResponse {
+    let etag = file.etag();
+    let last_modified = file.last_modified_timestamp();
+
+    if let Some(if_match) = headers.if_match {
+        if !EntityTagList::parse(if_match).matches(&etag, true) {
+            return ConditionalResponse::PreconditionFailed;
+        }
+    } else if let Some(if_unmodified_since) = headers.if_unmodified_since {
+        if let (Some(date), Some(last_modified)) =
+            (parse_http_date(if_unmodified_since), last_modified)
+        {
+            if last_modified > date {
+                return ConditionalResponse::PreconditionFailed;
+            }
+        }
+    }
+
+    let is_get_or_head = method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD");
+    if let Some(if_none_match) = headers.if_none_match {
+        if EntityTagList::parse(if_none_match).matches(&etag, false) {
+            return if is_get_or_head {
+                ConditionalResponse::NotModified
+            } else {
+                ConditionalResponse::PreconditionFailed
+            };
+        }
+    } else if let Some(if_modified_since) = headers.if_modified_since {
+        if let (true, Some(date), Some(last_modified)) = (
+            is_get_or_head,
+            parse_http_date(if_modified_since),
+            last_modified,
+        ) {
+            if last_modified <= date {
+                return ConditionalResponse::NotModified;
+            }
+        }
+    }
+
+    ConditionalResponse::Ok
+}
@@ -1,6 +1,8 @@
 #[cfg(feature = "include-exclude")]
 use globset::{Glob, GlobMatcher};
 
+use crate::IntegrityAlgorithm;
+
 #[derive(Debug)]
 pub struct Config {
     #[cfg(feature = "include-exclude")]
@@ -9,8 +11,30 @@ pub struct Config {
     exclude: Vec<GlobMatcher>,
     gzip: bool,
     br: bool,
+    zstd: bool,
+    gzip_level: u32,
+    zopfli: bool,
+    br_quality: u32,
+    br_window: u32,
+    zstd_level: i32,
+    compression_min_ratio: f64,
     preserve_source: bool,
     preserve_source_except: Vec<GlobMatcher>,
+    fallback: Option<String>,
+    fallback_extensionless_only: bool,
+    index_file: Option<String>,
+    allow_overrides: bool,
+    override_env: Option<String>,
+    hashed_names: bool,
+    integrity_algorithm: IntegrityAlgorithm,
+    last_modified: bool,
+    last_modified_git: bool,
+    #[cfg(feature = "include-exclude")]
+    cache_control: Vec<(GlobMatcher, String)>,
+    #[cfg(feature = "include-exclude")]
+    mime: Vec<(GlobMatcher, String)>,
+    default_mime: Option<String>,
+    sniff_mime: bool,
 }
 
 impl Default for Config {
@@ -22,9 +46,31 @@ impl Default for Config {
             exclude: vec![],
             gzip: true,
             br: true,
+            zstd: false,
+            gzip_level: 6,
+            zopfli: false,
+            br_quality: 11,
+            br_window: 22,
+            zstd_level: 3,
+            compression_min_ratio: 0.9,
             preserve_source: true,
             #[cfg(feature = "include-exclude")]
             preserve_source_except: vec![],
+            fallback: None,
+            fallback_extensionless_only: false,
+            index_file: None,
+            allow_overrides: false,
+            override_env: None,
+            hashed_names: false,
+            integrity_algorithm: IntegrityAlgorithm::Sha256,
+            last_modified: true,
+            last_modified_git: false,
+            #[cfg(feature = "include-exclude")]
+            cache_control: vec![],
+            #[cfg(feature = "include-exclude")]
+            mime: vec![],
+            default_mime: None,
+            sniff_mime: false,
         }
     }
 }
@@ -62,6 +108,34 @@ impl Config {
         );
     }
 
+    #[cfg(feature = "include-exclude")]
+    pub fn add_cache_control(&mut self, pattern: String, value: String) {
+        self.cache_control.push((
+            Glob::new(&pattern)
+                .expect("Failed to parse glob pattern for cache control")
+                .compile_matcher(),
+            value,
+        ));
+    }
+
+    #[cfg(feature = "include-exclude")]
+    pub fn add_mime(&mut self, pattern: String, mime_type: String) {
+        self.mime.push((
+            Glob::new(&pattern)
+                .expect("Failed to parse glob pattern for mime")
+                .compile_matcher(),
+            mime_type,
+        ));
+    }
+
+    pub fn set_default_mime(&mut self, mime_type: String) {
+        self.default_mime = Some(mime_type);
+    }
+
+    pub fn set_sniff_mime(&mut self, status: bool) {
+        self.sniff_mime = status;
+    }
+
     pub fn set_gzip(&mut self, status: bool) {
         self.gzip = status;
     }
@@ -70,10 +144,111 @@ impl Config {
         self.br = status;
     }
 
+    pub fn set_zstd(&mut self, status: bool) {
+        self.zstd = status;
+    }
+
+    pub fn set_gzip_level(&mut self, level: u32) {
+        if level > 9 {
+            panic!("gzip_level must be between 0 and 9, got {}", level);
+        }
+        self.gzip_level = level;
+    }
+
+    pub fn set_zopfli(&mut self, status: bool) {
+        self.zopfli = status;
+    }
+
+    pub fn set_br_quality(&mut self, quality: u32) {
+        if quality > 11 {
+            panic!("br_quality must be between 0 and 11, got {}", quality);
+        }
+        self.br_quality = quality;
+    }
+
+    pub fn set_br_window(&mut self, window: u32) {
+        if !(10..=24).contains(&window) {
+            panic!("br_window must be between 10 and 24, got {}", window);
+        }
+        self.br_window = window;
+    }
+
+    pub fn set_zstd_level(&mut self, level: i32) {
+        if !(1..=22).contains(&level) {
+            panic!("zstd_level must be between 1 and 22, got {}", level);
+        }
+        self.zstd_level = level;
+    }
+
+    pub fn set_compression_min_ratio(&mut self, ratio: f64) {
+        if !(ratio > 0.0 && ratio <= 1.0) {
+            panic!(
+                "compression_min_ratio must be larger than 0 and at most 1, got {}",
+                ratio
+            );
+        }
+        self.compression_min_ratio = ratio;
+    }
+
     pub fn set_preserve_source(&mut self, status: bool) {
         self.preserve_source = status;
     }
 
+    pub fn set_fallback(&mut self, path: String) {
+        self.fallback = Some(path);
+    }
+
+    pub fn set_fallback_extensionless_only(&mut self, status: bool) {
+        self.fallback_extensionless_only = status;
+    }
+
+    pub fn set_index_file(&mut self, name: String) {
+        if name.is_empty() || name.contains('/') {
+            panic!("index_file must be a file name, got {:?}", name);
+        }
+        self.index_file = Some(name);
+    }
+
+    pub fn set_allow_overrides(&mut self, status: bool) {
+        self.allow_overrides = status;
+    }
+
+    pub fn set_override_env(&mut self, name: String) {
+        if name.is_empty() {
+            panic!("override_env must be the name of an environment variable");
+        }
+        self.override_env = Some(name);
+    }
+
+    pub fn set_hashed_names(&mut self, status: bool) {
+        self.hashed_names = status;
+    }
+
+    pub fn set_last_modified(&mut self, status: bool) {
+        self.last_modified = status;
+    }
+
+    pub fn set_last_modified_source(&mut self, source: String) {
+        if source != "git" {
+            panic!(
+                "last_modified must be true, false or \"git\", got {:?}",
+                source
+            );
+        }
+        self.last_modified = true;
+        self.last_modified_git = true;
+    }
+
+    pub fn set_integrity_algorithm(&mut self, name: String) {
+        match IntegrityAlgorithm::from_name(&name) {
+            Some(algorithm) => self.integrity_algorithm = algorithm,
+            None => panic!(
+                "integrity must be one of sha256, sha384 or sha512, got {:?}",
+                name
+            ),
+        }
+    }
+
     #[cfg(feature = "include-exclude")]
     pub fn get_includes(&self) -> &Vec<GlobMatcher> {
         &self.include
@@ -89,6 +264,59 @@ impl Config {
         &self.preserve_source_except
     }
 
+    #[cfg(feature = "include-exclude")]
+    pub fn get_cache_controls(&self) -> &Vec<(GlobMatcher, String)> {
+        &self.cache_control
+    }
+
+    /// The `Cache-Control` header value for the file at the path. The first
+    /// pattern that matches the path wins.
+    pub fn get_cache_control(&self, path: &str) -> Option<&str> {
+        #[cfg(feature = "include-exclude")]
+        {
+            self.cache_control
+                .iter()
+                .find(|(matcher, _)| matcher.is_match(path))
+                .map(|(_, value)| value.as_str())
+        }
+        #[cfg(not(feature = "include-exclude"))]
+        {
+            None
+        }
+    }
+
+    #[cfg(feature = "include-exclude")]
+    pub fn get_mimes(&self) -> &Vec<(GlobMatcher, String)> {
+        &self.mime
+    }
+
+    /// The mime type set for the file at the path with a `mime` attribute.
+    /// The first pattern that matches the path wins.
+    pub fn get_mime_override(&self, path: &str) -> Option<&str> {
+        #[cfg(feature = "include-exclude")]
+        {
+            self.mime
+                .iter()
+                .find(|(matcher, _)| matcher.is_match(path))
+                .map(|(_, value)| value.as_str())
+        }
+        #[cfg(not(feature = "include-exclude"))]
+        {
+            None
+        }
+    }
+
+    /// The mime type for files that don't have one otherwise.
+    pub fn get_default_mime(&self) -> Option<&str> {
+        self.default_mime.as_deref()
+    }
+
+    /// Whether the mime type of files without an extension should be guessed
+    /// from their contents.
+    pub fn should_sniff_mime(&self) -> bool {
+        self.sniff_mime
+    }
+
     pub fn is_preserve_source_except(&self, path: &str) -> bool {
         #[cfg(feature = "include-exclude")]
         {
@@ -136,7 +364,91 @@ impl Config {
         self.br
     }
 
+    pub fn should_zstd(&self) -> bool {
+        self.zstd
+    }
+
+    pub fn get_gzip_level(&self) -> u32 {
+        self.gzip_level
+    }
+
+    /// Whether gzip compression should use zopfli, which is much slower but
+    /// produces smaller files.
+    pub fn should_zopfli(&self) -> bool {
+        self.zopfli
+    }
+
+    pub fn get_br_quality(&self) -> u32 {
+        self.br_quality
+    }
+
+    /// The base 2 logarithm of the brotli window size.
+    pub fn get_br_window(&self) -> u32 {
+        self.br_window
+    }
+
+    pub fn get_zstd_level(&self) -> i32 {
+        self.zstd_level
+    }
+
+    /// The largest size a compressed file can have, as a ratio of the original
+    /// size, for the compressed version to be included.
+    pub fn get_compression_min_ratio(&self) -> f64 {
+        self.compression_min_ratio
+    }
+
     pub fn should_preserve_source(&self) -> bool {
         self.preserve_source
     }
+
+    /// The file to return from `get_or_fallback` when the requested file
+    /// doesn't exist, relative to the folder.
+    pub fn get_fallback(&self) -> Option<&str> {
+        self.fallback.as_deref()
+    }
+
+    /// The name of the file that directory paths like `docs/` resolve to.
+    pub fn get_index_file(&self) -> Option<&str> {
+        self.index_file.as_deref()
+    }
+
+    /// Whether `get_or_fallback` should only fall back for paths without a
+    /// file extension.
+    pub fn is_fallback_extensionless_only(&self) -> bool {
+        self.fallback_extensionless_only
+    }
+
+    /// Whether a file in one folder may override the same file in a folder
+    /// that was added before it.
+    pub fn should_allow_overrides(&self) -> bool {
+        self.allow_overrides
+    }
+
+    /// The environment variable that points to a directory with files that
+    /// override the embedded ones at runtime.
+    pub fn get_override_env(&self) -> Option<&str> {
+        self.override_env.as_deref()
+    }
+
+    /// Whether files can also be found with the hash of their contents in the
+    /// file name, like `app.3fa9c1d2.js`.
+    pub fn should_hash_names(&self) -> bool {
+        self.hashed_names
+    }
+
+    /// The hash algorithm for the `integrity` values of files.
+    pub fn get_integrity_algorithm(&self) -> IntegrityAlgorithm {
+        self.integrity_algorithm
+    }
+
+    /// Whether files should have a last modified time.
+    pub fn should_include_last_modified(&self) -> bool {
+        self.last_modified
+    }
+
+    /// Whether the last modified time of embedded files is the time of the
+    /// last commit that changed them.
+    pub fn should_use_git_last_modified(&self) -> bool {
+        self.last_modified_git
+    }
 }
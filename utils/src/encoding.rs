@@ -0,0 +1,235 @@
+use std::{
+    borrow::Cow,
+    io::{BufReader, Read},
+};
+
+use flate2::read::GzDecoder;
+
+use crate::EmbedableFile;
+
+/// A content encoding that a file can be served with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// The file is not compressed.
+    Identity,
+    Gzip,
+    Br,
+    Zstd,
+}
+
+impl Encoding {
+    /// The name of the encoding, as it appears in `Accept-Encoding` headers.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            Encoding::Gzip => "gzip",
+            Encoding::Br => "br",
+            Encoding::Zstd => "zstd",
+        }
+    }
+
+    /// The value for the `Content-Encoding` header. This is `None` for
+    /// identity, in which case you should not send the header at all.
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            other => Some(other.name()),
+        }
+    }
+}
+
+/// The body picked for a response by `negotiate_encoding`.
+#[derive(Debug, Clone)]
+pub struct NegotiatedBody {
+    /// The data to send in the body of the response.
+    pub data: Cow<'static, [u8]>,
+    /// The encoding of the data.
+    pub encoding: Encoding,
+}
+
+/// Compressed encodings, in the order we prefer them when the client accepts
+/// several of them equally.
+const COMPRESSED_ENCODINGS: [Encoding; 3] = [Encoding::Br, Encoding::Zstd, Encoding::Gzip];
+
+/// The smallest quality value a client can send. Identity gets this value
+/// when the client does not mention it.
+const IMPLICIT_IDENTITY_QUALITY: f32 = 0.001;
+
+/// Parse a quality value, which is a number between 0 and 1 with at most three
+/// decimals. Returns `None` for anything else, including values like `nan` or
+/// `2` that a float parser would accept.
+fn parse_quality(value: &str) -> Option<f32> {
+    let (whole, decimals) = match value.split_once('.') {
+        Some((whole, decimals)) => (whole, decimals),
+        None => (value, ""),
+    };
+    if decimals.len() > 3 || !decimals.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    match whole {
+        "0" => value.parse().ok(),
+        "1" if decimals.bytes().all(|byte| byte == b'0') => Some(1.0),
+        _ => None,
+    }
+}
+
+/// The parsed contents of an `Accept-Encoding` header.
+struct AcceptEncoding<'t> {
+    /// Content codings and their quality values, in lowercase.
+    codings: Vec<(Cow<'t, str>, f32)>,
+}
+
+impl<'t> AcceptEncoding<'t> {
+    fn parse(header: &'t str) -> Self {
+        let codings = header
+            .split(',')
+            .filter_map(|element| {
+                let mut parts = element.split(';').map(str::trim);
+                let coding = parts.next().filter(|v| !v.is_empty())?;
+                let mut quality = 1.0;
+                for param in parts {
+                    if let Some((name, value)) = param.split_once('=') {
+                        if name.trim().eq_ignore_ascii_case("q") {
+                            // Ignore the element entirely if the quality
+                            // value is malformed.
+                            quality = parse_quality(value.trim())?;
+                        }
+                    }
+                }
+                let coding = if coding.eq_ignore_ascii_case("x-gzip") {
+                    Cow::from("gzip")
+                } else if coding.chars().any(|c| c.is_ascii_uppercase()) {
+                    Cow::from(coding.to_ascii_lowercase())
+                } else {
+                    Cow::from(coding)
+                };
+                Some((coding, quality))
+            })
+            .collect();
+        AcceptEncoding { codings }
+    }
+
+    fn explicit_quality(&self, name: &str) -> Option<f32> {
+        self.codings
+            .iter()
+            .find(|(coding, _)| coding == name)
+            .map(|(_, quality)| *quality)
+    }
+
+    fn quality(&self, encoding: Encoding) -> f32 {
+        let explicit = self
+            .explicit_quality(encoding.name())
+            .or_else(|| self.explicit_quality("*"));
+        match encoding {
+            // Identity is always acceptable unless it's explicitly refused,
+            // but it's the least preferred if it's not mentioned.
+            Encoding::Identity => explicit.unwrap_or(IMPLICIT_IDENTITY_QUALITY),
+            _ => explicit.unwrap_or(0.0),
+        }
+    }
+}
+
+fn compressed_data<F: EmbedableFile>(file: &F, encoding: Encoding) -> Option<Cow<'static, [u8]>> {
+    match encoding {
+        Encoding::Identity => file.data(),
+        Encoding::Gzip => file.data_gzip(),
+        Encoding::Br => file.data_br(),
+        Encoding::Zstd => file.data_zstd(),
+    }
+}
+
+/// Get the uncompressed data for the file, decompressing one of the
+/// compressed versions if the original data was not embedded.
+pub(crate) fn identity_data<F: EmbedableFile>(file: &F) -> Option<Cow<'static, [u8]>> {
+    if let Some(data) = file.data() {
+        return Some(data);
+    }
+    let mut data = Vec::new();
+    if let Some(data_gzip) = file.data_gzip() {
+        GzDecoder::new(data_gzip.as_ref())
+            .read_to_end(&mut data)
+            .expect("Failed to decompress gzip data");
+        return Some(Cow::from(data));
+    }
+    if let Some(data_br) = file.data_br() {
+        brotli::BrotliDecompress(&mut BufReader::new(data_br.as_ref()), &mut data)
+            .expect("Failed to decompress br data");
+        return Some(Cow::from(data));
+    }
+    #[cfg(feature = "zstd")]
+    if let Some(data_zstd) = file.data_zstd() {
+        let data = zstd::decode_all(data_zstd.as_ref()).expect("Failed to decompress zstd data");
+        return Some(Cow::from(data));
+    }
+    None
+}
+
+/// Pick the body to send for a file, based on the `Accept-Encoding` header of
+/// the request.
+///
+/// Pass the value of the `Accept-Encoding` header, or `None` if the request
+/// didn't have one. If the request has several `Accept-Encoding` headers, join
+/// them with commas. Quality values, `identity` and `*` are all respected. When
+/// the client accepts several encodings equally, brotli is preferred, then
+/// zstd, then gzip.
+///
+/// If the client only accepts the uncompressed file but the file was embedded
+/// with `preserve_source = false`, the uncompressed data is recovered by
+/// decompressing one of the compressed versions.
+///
+/// Returns `None` if the file is not available in any encoding the client
+/// accepts, in which case you could respond with `406 Not Acceptable`.
+///
+/// Since the response depends on the `Accept-Encoding` header, remember to
+/// add `Vary: Accept-Encoding` to your responses.
+///
+/// ```
+/// # use rust_embed_for_web_utils::{negotiate_encoding, EmbedableFile};
+/// fn respond<F: EmbedableFile>(accept_encoding: Option<&str>, file: &F) {
+///     if let Some(body) = negotiate_encoding(accept_encoding, file) {
+///         if let Some(content_encoding) = body.encoding.content_encoding() {
+///             println!("Content-Encoding: {}", content_encoding);
+///         }
+///         println!("Content-Length: {}", body.data.len());
+///     }
+/// }
+/// ```
+pub fn negotiate_encoding<F: EmbedableFile>(
+    accept_encoding: Option<&str>,
+    file: &F,
+) -> Option<NegotiatedBody> {
+    let accept_encoding = match accept_encoding {
+        Some(header) => AcceptEncoding::parse(header),
+        // Without the header any encoding is acceptable, but the client is
+        // most likely not going to expect a compressed response.
+        None => {
+            return identity_data(file).map(|data| NegotiatedBody {
+                data,
+                encoding: Encoding::Identity,
+            })
+        }
+    };
+
+    let identity_quality = accept_encoding.quality(Encoding::Identity);
+    let mut best: Option<(f32, Encoding, Cow<'static, [u8]>)> = None;
+    for encoding in COMPRESSED_ENCODINGS {
+        let quality = accept_encoding.quality(encoding);
+        if quality <= 0.0 || best.as_ref().is_some_and(|(q, _, _)| quality <= *q) {
+            continue;
+        }
+        if let Some(data) = compressed_data(file, encoding) {
+            best = Some((quality, encoding, data));
+        }
+    }
+
+    match best {
+        Some((quality, encoding, data)) if quality >= identity_quality => {
+            Some(NegotiatedBody { data, encoding })
+        }
+        _ if identity_quality > 0.0 => identity_data(file).map(|data| NegotiatedBody {
+            data,
+            encoding: Encoding::Identity,
+        }),
+        _ => None,
+    }
+}
@@ -45,8 +45,32 @@ pub trait EmbedableFile {
     ///
     /// This is `Some` if precompression has been done. `None` if the file was
     /// not precompressed, either because the file doesn't benefit from
-    /// compression or because gzip was disabled with `#[br = false]`.
+    /// compression or because brotli was disabled with `#[br = false]`.
     fn data_br(&self) -> Option<Cow<'static, [u8]>>;
+    /// The contents of the file, compressed with zstd.
+    ///
+    /// This is `Some` if precompression has been done. `None` if the file was
+    /// not precompressed, either because the file doesn't benefit from
+    /// compression or because zstd was not enabled with `#[zstd = true]`.
+    fn data_zstd(&self) -> Option<Cow<'static, [u8]>>;
+    /// The length of the contents of the file in bytes.
+    ///
+    /// This is available even if the contents were not embedded with
+    /// `#[preserve_source = false]`, and doesn't need to copy them.
+    fn len(&self) -> usize;
+    /// Whether the file is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The length of the contents of the file compressed with gzip, if the
+    /// file was compressed with gzip.
+    fn gzip_len(&self) -> Option<usize>;
+    /// The length of the contents of the file compressed with brotli, if the
+    /// file was compressed with brotli.
+    fn br_len(&self) -> Option<usize>;
+    /// The length of the contents of the file compressed with zstd, if the
+    /// file was compressed with zstd.
+    fn zstd_len(&self) -> Option<usize>;
     /// The UNIX timestamp of when the file was last modified.
     fn last_modified_timestamp(&self) -> Option<i64>;
     /// The rfc2822 encoded last modified date. This is the format you use for
@@ -57,7 +81,14 @@ pub trait EmbedableFile {
     /// The ETag value for the file. This is just the file hash, wrapped with
     /// quote symbols.
     fn etag(&self) -> Cow<'static, str>;
+    /// The subresource integrity value for the file, like `sha256-<base64>`.
+    /// You can use this in the `integrity` attribute of `<script>` and
+    /// `<link>` tags.
+    fn integrity(&self) -> Cow<'static, str>;
     /// The mime type for the file, if one can be guessed from the file
     /// extension.
     fn mime_type(&self) -> Option<Cow<'static, str>>;
+    /// The `Cache-Control` header value for the file, if one was set with a
+    /// `#[cache_control("*.html", "no-cache")]` attribute.
+    fn cache_control(&self) -> Option<Cow<'static, str>>;
 }
@@ -8,10 +8,10 @@ use std::{
 };
 
 use chrono::TimeZone;
-use new_mime_guess::MimeGuess;
 use sha2::{Digest, Sha256};
 
 use super::common::EmbedableFile;
+use crate::{guess_mime_type, Config, IntegrityAlgorithm};
 
 /// A file read from the file system dynamically.
 ///
@@ -25,8 +25,10 @@ pub struct DynamicFile {
     name: String,
     data: Vec<u8>,
     hash: String,
+    integrity_algorithm: IntegrityAlgorithm,
     last_modified_timestamp: Option<i64>,
     mime_type: Option<String>,
+    cache_control: Option<String>,
 }
 
 impl EmbedableFile for DynamicFile {
@@ -46,6 +48,26 @@ impl EmbedableFile for DynamicFile {
         None
     }
 
+    fn data_zstd(&self) -> Option<Cow<'static, [u8]>> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn gzip_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn br_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn zstd_len(&self) -> Option<usize> {
+        None
+    }
+
     fn last_modified(&self) -> Option<Cow<'static, str>> {
         self.last_modified_timestamp
             .map(|v| Cow::from(chrono::Utc.timestamp_opt(v, 0).unwrap().to_rfc2822()))
@@ -63,9 +85,18 @@ impl EmbedableFile for DynamicFile {
         Cow::from(format!("\"{}\"", self.hash))
     }
 
+    fn integrity(&self) -> Cow<'static, str> {
+        // Computed when needed, because dynamic files are read on every request
+        Cow::from(self.integrity_algorithm.integrity(&self.data))
+    }
+
     fn mime_type(&self) -> Option<Cow<'static, str>> {
         self.mime_type.as_ref().map(|v| Cow::from(v.clone()))
     }
+
+    fn cache_control(&self) -> Option<Cow<'static, str>> {
+        self.cache_control.as_ref().map(|v| Cow::from(v.clone()))
+    }
 }
 
 fn modified_unix_timestamp(metadata: &std::fs::Metadata) -> Option<i64> {
@@ -97,7 +128,8 @@ impl DynamicFile {
         let hash = hasher.finalize();
         let hash = base85rs::encode(&hash[..]);
 
-        let mime_type = MimeGuess::from_path(&path).first().map(|v| v.to_string());
+        let mime_type =
+            guess_mime_type(&Config::default(), &path.as_ref().to_string_lossy(), &data);
         let name = Path::file_name(path.as_ref())
             .expect("Unable to parse the file name")
             .to_string_lossy()
@@ -107,10 +139,37 @@ impl DynamicFile {
             name,
             data,
             hash,
+            integrity_algorithm: IntegrityAlgorithm::default(),
             last_modified_timestamp,
             mime_type,
+            cache_control: None,
         })
     }
+
+    /// Use a different last modified time, or none at all, instead of the
+    /// modification time of the file.
+    pub fn with_last_modified_timestamp(mut self, timestamp: Option<i64>) -> Self {
+        self.last_modified_timestamp = timestamp;
+        self
+    }
+
+    /// Use a different mime type for the file.
+    pub fn with_mime_type(mut self, mime_type: Option<String>) -> Self {
+        self.mime_type = mime_type;
+        self
+    }
+
+    /// Set the `Cache-Control` header value for the file.
+    pub fn with_cache_control(mut self, cache_control: Option<String>) -> Self {
+        self.cache_control = cache_control;
+        self
+    }
+
+    /// Use a different algorithm for `integrity`, instead of sha256.
+    pub fn with_integrity_algorithm(mut self, algorithm: IntegrityAlgorithm) -> Self {
+        self.integrity_algorithm = algorithm;
+        self
+    }
 }
 
 impl Debug for DynamicFile {
@@ -15,11 +15,15 @@ pub struct EmbeddedFile {
     data: Option<&'static [u8]>,
     data_gzip: Option<&'static [u8]>,
     data_br: Option<&'static [u8]>,
+    data_zstd: Option<&'static [u8]>,
+    len: usize,
     hash: &'static str,
     etag: &'static str,
+    integrity: &'static str,
     last_modified: Option<&'static str>,
     last_modified_timestamp: Option<i64>,
     mime_type: Option<&'static str>,
+    cache_control: Option<&'static str>,
 }
 
 impl EmbedableFile for EmbeddedFile {
@@ -39,6 +43,26 @@ impl EmbedableFile for EmbeddedFile {
         self.data_br.map(Cow::from)
     }
 
+    fn data_zstd(&self) -> Option<Cow<'static, [u8]>> {
+        self.data_zstd.map(Cow::from)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn gzip_len(&self) -> Option<usize> {
+        self.data_gzip.map(|data| data.len())
+    }
+
+    fn br_len(&self) -> Option<usize> {
+        self.data_br.map(|data| data.len())
+    }
+
+    fn zstd_len(&self) -> Option<usize> {
+        self.data_zstd.map(|data| data.len())
+    }
+
     fn last_modified(&self) -> Option<Cow<'static, str>> {
         self.last_modified.map(Cow::from)
     }
@@ -55,9 +79,17 @@ impl EmbedableFile for EmbeddedFile {
         Cow::from(self.etag)
     }
 
+    fn integrity(&self) -> Cow<'static, str> {
+        Cow::from(self.integrity)
+    }
+
     fn mime_type(&self) -> Option<Cow<'static, str>> {
         self.mime_type.map(Cow::from)
     }
+
+    fn cache_control(&self) -> Option<Cow<'static, str>> {
+        self.cache_control.map(Cow::from)
+    }
 }
 
 impl EmbeddedFile {
@@ -65,7 +97,7 @@ impl EmbeddedFile {
     #[allow(clippy::too_many_arguments)]
     /// This is used internally in derived code to create embedded file objects.
     /// You don't want to manually use this function!
-    pub fn __internal_make(
+    pub const fn __internal_make(
         // Make sure that the order of these parameters is correct in respect to
         // the file contents! And if you are changing or reordering any of
         // these, make sure to update the corresponding call in `impl`
@@ -73,24 +105,55 @@ impl EmbeddedFile {
         data: Option<&'static [u8]>,
         data_gzip: Option<&'static [u8]>,
         data_br: Option<&'static [u8]>,
+        data_zstd: Option<&'static [u8]>,
+        len: usize,
         hash: &'static str,
         etag: &'static str,
+        integrity: &'static str,
         last_modified: Option<&'static str>,
         last_modified_timestamp: Option<i64>,
         mime_type: Option<&'static str>,
+        cache_control: Option<&'static str>,
     ) -> EmbeddedFile {
         EmbeddedFile {
             name,
             data,
             data_gzip,
             data_br,
+            data_zstd,
+            len,
             hash,
             etag,
+            integrity,
             last_modified,
             last_modified_timestamp,
             mime_type,
+            cache_control,
         }
     }
+
+    #[doc(hidden)]
+    /// This is used internally in derived code to find files in the table of
+    /// embedded files, which is sorted by path. Aliases like the paths of
+    /// directories with index files point to an entry in the table, and are
+    /// sorted too. Returns the entry, so the path of the file an alias points
+    /// to is known. You don't want to manually use this function!
+    pub fn __internal_find(
+        files: &[(&'static str, EmbeddedFile)],
+        aliases: &[(&'static str, usize)],
+        path: &str,
+    ) -> Option<(&'static str, EmbeddedFile)> {
+        let index = match files.binary_search_by(|(file_path, _)| (*file_path).cmp(path)) {
+            Ok(index) => index,
+            Err(_) => {
+                let alias = aliases
+                    .binary_search_by(|(alias_path, _)| (*alias_path).cmp(path))
+                    .ok()?;
+                aliases[alias].1
+            }
+        };
+        files.get(index).copied()
+    }
 }
 
 impl Debug for EmbeddedFile {
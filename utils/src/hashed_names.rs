@@ -0,0 +1,71 @@
+/// The number of bytes of the file hash that go into hashed file names.
+const SHORT_HASH_BYTES: usize = 4;
+
+fn short_hash(hash: &str) -> String {
+    let hash = base85rs::decode(hash).expect("File hash is not valid base85");
+    hash.iter()
+        .take(SHORT_HASH_BYTES)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn is_short_hash(value: &str) -> bool {
+    value.len() == SHORT_HASH_BYTES * 2
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+fn split_file_name(path: &str) -> (&str, &str) {
+    match path.rfind('/') {
+        Some(index) => path.split_at(index + 1),
+        None => ("", path),
+    }
+}
+
+/// Add the hash of a file to its path, like `js/app.js` to `js/app.3fa9c1d2.js`.
+///
+/// The hash is the `hash` of the file. Paths of directories like `docs/` are
+/// returned as is.
+pub fn hashed_path(path: &str, hash: &str) -> String {
+    let (directory, file_name) = split_file_name(path);
+    if file_name.is_empty() {
+        return path.to_string();
+    }
+    let short_hash = short_hash(hash);
+    match file_name.rfind('.') {
+        // A leading dot like `.htaccess` is not an extension
+        Some(index) if index > 0 => format!(
+            "{}{}.{}{}",
+            directory,
+            &file_name[..index],
+            short_hash,
+            &file_name[index..]
+        ),
+        _ => format!("{}{}.{}", directory, file_name, short_hash),
+    }
+}
+
+/// Remove the hash from a path made by `hashed_path`.
+///
+/// This doesn't check if the hash is correct, only that the path looks like
+/// one that has a hash in it.
+pub fn unhashed_path(path: &str) -> Option<String> {
+    let (directory, file_name) = split_file_name(path);
+    let extension_start = file_name.rfind('.')?;
+    let stem = &file_name[..extension_start];
+    if let Some(hash_start) = stem.rfind('.') {
+        if hash_start > 0 && is_short_hash(&stem[hash_start + 1..]) {
+            return Some(format!(
+                "{}{}{}",
+                directory,
+                &stem[..hash_start],
+                &file_name[extension_start..]
+            ));
+        }
+    }
+    if extension_start > 0 && is_short_hash(&file_name[extension_start + 1..]) {
+        return Some(format!("{}{}", directory, stem));
+    }
+    None
+}
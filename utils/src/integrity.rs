@@ -0,0 +1,45 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// The hash algorithm for subresource integrity values.
+///
+/// You can pick the algorithm with the `#[integrity = "sha384"]` attribute.
+/// The default is sha256.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegrityAlgorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl IntegrityAlgorithm {
+    /// Parse the name of the algorithm, like `sha384`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(IntegrityAlgorithm::Sha256),
+            "sha384" => Some(IntegrityAlgorithm::Sha384),
+            "sha512" => Some(IntegrityAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    /// The name of the algorithm, as used in integrity values.
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntegrityAlgorithm::Sha256 => "sha256",
+            IntegrityAlgorithm::Sha384 => "sha384",
+            IntegrityAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Compute the integrity value for some data, like `sha256-<base64>`.
+    pub fn integrity(&self, data: &[u8]) -> String {
+        let hash = match self {
+            IntegrityAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+            IntegrityAlgorithm::Sha384 => Sha384::digest(data).to_vec(),
+            IntegrityAlgorithm::Sha512 => Sha512::digest(data).to_vec(),
+        };
+        format!("{}-{}", self.name(), STANDARD.encode(hash))
+    }
+}
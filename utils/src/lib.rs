@@ -10,6 +10,33 @@ pub use file::*;
 mod config;
 pub use config::Config;
 
+mod path;
+pub use path::resolve_in_folder;
+
+mod mime;
+pub use mime::guess_mime_type;
+
+mod integrity;
+pub use integrity::IntegrityAlgorithm;
+
+mod hashed_names;
+pub use hashed_names::{hashed_path, unhashed_path};
+
+mod conditional;
+pub use conditional::{evaluate_conditional, ConditionalHeaders, ConditionalResponse};
+
+mod encoding;
+pub use encoding::{negotiate_encoding, Encoding, NegotiatedBody};
+
+mod range;
+pub use range::{evaluate_range, RangeResponse};
+
+#[doc(hidden)]
+pub mod response;
+
+#[cfg(feature = "actix")]
+mod actix;
+
 pub struct FileEntry {
     pub rel_path: String,
     pub full_canonical_path: String,
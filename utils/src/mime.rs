@@ -0,0 +1,73 @@
+use new_mime_guess::MimeGuess;
+
+use crate::Config;
+
+/// Mime types that are text, other than `text/*`.
+const TEXT_MIME_TYPES: &[&str] = &[
+    "application/ecmascript",
+    "application/javascript",
+    "application/json",
+    "application/xml",
+    "image/svg+xml",
+];
+
+fn is_text_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("text/")
+        || mime_type.ends_with("+json")
+        || mime_type.ends_with("+xml")
+        || TEXT_MIME_TYPES.contains(&mime_type)
+}
+
+fn has_extension(path: &str) -> bool {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+    // A leading dot like `.htaccess` is not an extension
+    file_name.rfind('.').is_some_and(|index| index > 0)
+}
+
+/// Guess the mime type from the contents of a file.
+fn sniff_mime_type(data: &[u8]) -> Option<String> {
+    if let Some(kind) = infer::get(data) {
+        return Some(kind.mime_type().to_string());
+    }
+    if !data.contains(&0) && std::str::from_utf8(data).is_ok() {
+        return Some("text/plain".to_string());
+    }
+    None
+}
+
+/// Find the mime type of a file. The `path` is the path you would pass to
+/// `get`.
+///
+/// The mime type comes from the first of these that works:
+///
+/// - A `#[mime("*.foo", "application/x-foo")]` attribute that matches the path
+/// - The file extension
+/// - The contents of the file, if the file has no extension and
+///   `#[sniff_mime = true]` is set
+/// - The `#[default_mime = "..."]` attribute
+///
+/// Text types get a `; charset=utf-8` parameter if the file is valid UTF-8.
+pub fn guess_mime_type(config: &Config, path: &str, data: &[u8]) -> Option<String> {
+    let mime_type = config
+        .get_mime_override(path)
+        .map(String::from)
+        .or_else(|| MimeGuess::from_path(path).first().map(|v| v.to_string()))
+        .or_else(|| {
+            if config.should_sniff_mime() && !has_extension(path) {
+                sniff_mime_type(data)
+            } else {
+                None
+            }
+        })
+        .or_else(|| config.get_default_mime().map(String::from))?;
+
+    // Leave any parameters that were set by an override alone
+    if !mime_type.contains(';')
+        && is_text_mime_type(&mime_type)
+        && std::str::from_utf8(data).is_ok()
+    {
+        Some(format!("{}; charset=utf-8", mime_type))
+    } else {
+        Some(mime_type)
+    }
+}
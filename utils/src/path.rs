@@ -0,0 +1,130 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Find the file at a relative path inside a folder, making sure that the path
+/// can't escape the folder.
+///
+/// Paths that contain NUL bytes, empty, `.` or `..` components, or that are
+/// absolute are rejected, the same as they are by embedded files. Both `/` and
+/// `\` are treated as separators on all platforms. The file has to exist, and its canonical
+/// path has to be inside the canonical path of the folder, so symlinks that
+/// point outside of the folder are rejected too.
+///
+/// Returns the canonical path of the file if it is safe to read.
+pub fn resolve_in_folder<P: AsRef<Path>>(folder: P, path: &str) -> Option<PathBuf> {
+    if path.contains('\0') {
+        return None;
+    }
+    let mut combined_path = folder.as_ref().to_path_buf();
+    for part in path.split(['/', '\\']) {
+        // An empty first part means that the path starts with a separator
+        if part.is_empty() || part == "." {
+            return None;
+        }
+        // This catches `..`, as well as things like drive prefixes on Windows
+        match Path::new(part).components().collect::<Vec<_>>().as_slice() {
+            [Component::Normal(part)] => combined_path.push(part),
+            _ => return None,
+        }
+    }
+
+    let canonical_folder = std::fs::canonicalize(folder).ok()?;
+    let canonical_path = std::fs::canonicalize(combined_path).ok()?;
+    if canonical_path.starts_with(canonical_folder) {
+        Some(canonical_path)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+
+    /// A folder with `public/index.html`, and `secret.txt` next to it.
+    fn test_folder(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!(
+            "rust-embed-for-web-path-{}-{}",
+            name,
+            process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("public/images")).unwrap();
+        fs::write(root.join("public/index.html"), "index").unwrap();
+        fs::write(root.join("public/images/llama.png"), "llama").unwrap();
+        fs::write(root.join("secret.txt"), "secret").unwrap();
+        root
+    }
+
+    #[test]
+    fn files_in_the_folder_are_found() {
+        let root = test_folder("found");
+        let public = root.join("public");
+        let index = resolve_in_folder(&public, "index.html").unwrap();
+        assert_eq!(fs::read(index).unwrap(), b"index");
+        assert!(resolve_in_folder(&public, "images/llama.png").is_some());
+        assert!(resolve_in_folder(&public, "images\\llama.png").is_some());
+        assert!(resolve_in_folder(&public, "missing.html").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn parent_directories_are_rejected() {
+        let root = test_folder("parent");
+        let public = root.join("public");
+        assert!(resolve_in_folder(&public, "../secret.txt").is_none());
+        assert!(resolve_in_folder(&public, "..\\secret.txt").is_none());
+        assert!(resolve_in_folder(&public, "images/../../secret.txt").is_none());
+        assert!(resolve_in_folder(&public, "images/../index.html").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn absolute_paths_are_rejected() {
+        let root = test_folder("absolute");
+        let public = root.join("public");
+        let secret = root.join("secret.txt");
+        assert!(resolve_in_folder(&public, secret.to_str().unwrap()).is_none());
+        assert!(resolve_in_folder(&public, "/index.html").is_none());
+        assert!(resolve_in_folder(&public, "\\index.html").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn empty_and_current_directory_segments_are_rejected() {
+        let root = test_folder("empty");
+        let public = root.join("public");
+        assert!(resolve_in_folder(&public, "").is_none());
+        assert!(resolve_in_folder(&public, "./index.html").is_none());
+        assert!(resolve_in_folder(&public, "images//llama.png").is_none());
+        assert!(resolve_in_folder(&public, "images/./llama.png").is_none());
+        assert!(resolve_in_folder(&public, "images/").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn nul_bytes_are_rejected() {
+        let root = test_folder("nul");
+        let public = root.join("public");
+        assert!(resolve_in_folder(&public, "index.html\0").is_none());
+        assert!(resolve_in_folder(&public, "index.html\0.png").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_folder_are_rejected() {
+        use std::os::unix::fs::symlink;
+
+        let root = test_folder("symlink");
+        let public = root.join("public");
+        symlink(root.join("secret.txt"), public.join("outside.txt")).unwrap();
+        symlink("index.html", public.join("inside.html")).unwrap();
+        symlink(&root, public.join("parent")).unwrap();
+        assert!(resolve_in_folder(&public, "inside.html").is_some());
+        assert!(resolve_in_folder(&public, "outside.txt").is_none());
+        assert!(resolve_in_folder(&public, "parent/secret.txt").is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,223 @@
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    conditional::{parse_http_date, EntityTagList},
+    encoding::identity_data,
+    EmbedableFile,
+};
+
+/// The most ranges we'll serve in a single response. Requests with more ranges
+/// get the full file instead, to avoid spending a lot of effort on abusive
+/// requests.
+const MAX_RANGES: usize = 32;
+
+/// How a request with a `Range` header should be answered.
+#[derive(Debug, Clone)]
+pub enum RangeResponse {
+    /// Respond with the full file as usual. This happens when there is no
+    /// `Range` header, the header is invalid, or the `If-Range` condition
+    /// failed.
+    Full,
+    /// Respond with `206 Partial Content`, with the given data as the body.
+    /// Add a `Content-Range` header with the `content_range` value.
+    Partial {
+        data: Cow<'static, [u8]>,
+        content_range: String,
+    },
+    /// Respond with `206 Partial Content`, with the given
+    /// `multipart/byteranges` data as the body. Use the `content_type` value
+    /// as the `Content-Type` header instead of the mime type of the file.
+    Multipart { data: Vec<u8>, content_type: String },
+    /// Respond with `416 Range Not Satisfiable`. Add a `Content-Range` header
+    /// with the `content_range` value.
+    NotSatisfiable { content_range: String },
+}
+
+/// Parse the value of a `Range` header into byte ranges for a file of some
+/// length. Returns `None` if the header is invalid or uses a unit other than
+/// bytes, and an empty list if none of the ranges are satisfiable.
+fn parse_range(header: &str, length: usize) -> Option<Vec<Range<usize>>> {
+    let (unit, ranges) = header.split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let mut satisfiable = Vec::new();
+    for range in ranges.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+        let (first, last) = range.split_once('-')?;
+        let (first, last) = (first.trim(), last.trim());
+        let range = if first.is_empty() {
+            // A suffix range like `-500`, for the last 500 bytes
+            let suffix_length: usize = last.parse().ok()?;
+            length.saturating_sub(suffix_length)..length
+        } else {
+            let first: usize = first.parse().ok()?;
+            let last = if last.is_empty() {
+                length
+            } else {
+                let last: usize = last.parse().ok()?;
+                if last < first {
+                    return None;
+                }
+                // The last position is inclusive, and can go past the end
+                last.saturating_add(1).min(length)
+            };
+            first..last
+        };
+        if range.start < range.end {
+            satisfiable.push(range);
+        }
+    }
+    Some(satisfiable)
+}
+
+/// Check the `If-Range` header, which makes the `Range` header apply only if
+/// the file has not changed.
+fn if_range_matches<F: EmbedableFile>(if_range: &str, file: &F) -> bool {
+    let if_range = if_range.trim();
+    if if_range.starts_with('"') || if_range.starts_with("W/") {
+        EntityTagList::parse(if_range).matches(&file.etag(), true)
+    } else {
+        match (parse_http_date(if_range), file.last_modified_timestamp()) {
+            (Some(date), Some(last_modified)) => date == last_modified,
+            _ => false,
+        }
+    }
+}
+
+/// Merge ranges that overlap or are next to each other, and sort them. Clients
+/// are supposed to do that themselves, but they don't have to.
+fn coalesce(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut coalesced: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match coalesced.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => coalesced.push(range),
+        }
+    }
+    coalesced
+}
+
+fn slice(data: Cow<'static, [u8]>, range: Range<usize>) -> Cow<'static, [u8]> {
+    match data {
+        Cow::Borrowed(data) => Cow::Borrowed(&data[range]),
+        Cow::Owned(mut data) => {
+            data.truncate(range.end);
+            data.drain(..range.start);
+            Cow::Owned(data)
+        }
+    }
+}
+
+fn content_range(range: &Range<usize>, length: usize) -> String {
+    format!("bytes {}-{}/{}", range.start, range.end - 1, length)
+}
+
+/// Decide how to answer a request with a `Range` header.
+///
+/// Pass the values of the `Range` and `If-Range` headers, or `None` if they
+/// are missing. Ranges always apply to the uncompressed file, so don't add a
+/// `Content-Encoding` header to partial responses. If the file was embedded
+/// with `preserve_source = false`, the uncompressed data is recovered by
+/// decompressing one of the compressed versions.
+///
+/// Overlapping and adjacent ranges are merged. If the ranges add up to more
+/// than the whole file, the full file is served instead.
+///
+/// You should check `evaluate_conditional` first, and only use this if it
+/// returns `ConditionalResponse::Ok`. Remember to add an `Accept-Ranges: bytes`
+/// header to your responses to let clients know that they can use ranges.
+///
+/// ```
+/// # use rust_embed_for_web_utils::{evaluate_range, EmbedableFile, RangeResponse};
+/// fn respond<F: EmbedableFile>(range: Option<&str>, file: &F) {
+///     match evaluate_range(range, None, file) {
+///         RangeResponse::Full => println!("200 OK"),
+///         RangeResponse::Partial { content_range, .. } => {
+///             println!("206 Partial Content");
+///             println!("Content-Range: {}", content_range);
+///         }
+///         RangeResponse::Multipart { content_type, .. } => {
+///             println!("206 Partial Content");
+///             println!("Content-Type: {}", content_type);
+///         }
+///         RangeResponse::NotSatisfiable { content_range } => {
+///             println!("416 Range Not Satisfiable");
+///             println!("Content-Range: {}", content_range);
+///         }
+///     }
+/// }
+/// ```
+pub fn evaluate_range<F: EmbedableFile>(
+    range: Option<&str>,
+    if_range: Option<&str>,
+    file: &F,
+) -> RangeResponse {
+    let range = match range {
+        Some(range) => range,
+        None => return RangeResponse::Full,
+    };
+    if let Some(if_range) = if_range {
+        if !if_range_matches(if_range, file) {
+            return RangeResponse::Full;
+        }
+    }
+    let length = file.len();
+    let ranges = match parse_range(range, length) {
+        Some(ranges) if ranges.len() <= MAX_RANGES => ranges,
+        _ => return RangeResponse::Full,
+    };
+    // Overlapping ranges could ask for many copies of the file. That isn't a
+    // reasonable request, so it gets the file once instead.
+    let requested: usize = ranges.iter().map(|range| range.len()).sum();
+    if requested > length {
+        return RangeResponse::Full;
+    }
+    let ranges = coalesce(ranges);
+    // Only decompress the file once we know that ranges of it will be served
+    let data = match identity_data(file) {
+        Some(data) if data.len() == length => data,
+        _ => return RangeResponse::Full,
+    };
+
+    match ranges.as_slice() {
+        [] => RangeResponse::NotSatisfiable {
+            content_range: format!("bytes */{}", length),
+        },
+        [range] => RangeResponse::Partial {
+            data: slice(data, range.clone()),
+            content_range: content_range(range, length),
+        },
+        ranges => {
+            // The hash is derived from the contents, so it's very unlikely to
+            // show up inside the file.
+            let boundary: String = file
+                .hash()
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect();
+            let mime_type = file
+                .mime_type()
+                .unwrap_or(Cow::Borrowed("application/octet-stream"));
+            let mut body = Vec::new();
+            for range in ranges {
+                body.extend_from_slice(
+                    format!(
+                        "--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+                        boundary,
+                        mime_type,
+                        content_range(range, length)
+                    )
+                    .as_bytes(),
+                );
+                body.extend_from_slice(&data[range.clone()]);
+                body.extend_from_slice(b"\r\n");
+            }
+            body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+            RangeResponse::Multipart {
+                data: body,
+                content_type: format!("multipart/byteranges; boundary={}", boundary),
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Framework independent logic for responding to a request for a file. The
+//! framework integrations translate requests and responses to and from the
+//! types here.
+//!
+//! This is only public so `rust-embed-for-web` can use it, you don't want to
+//! use it directly.
+use std::borrow::Cow;
+
+use crate::{
+    evaluate_conditional, evaluate_range, negotiate_encoding, ConditionalHeaders,
+    ConditionalResponse, EmbedableFile, RangeResponse,
+};
+
+/// The headers of a request that matter when serving a file.
+#[derive(Debug, Default)]
+pub struct RequestHeaders<'t> {
+    pub accept_encoding: Option<&'t str>,
+    pub conditional: ConditionalHeaders<'t>,
+    pub range: Option<&'t str>,
+    pub if_range: Option<&'t str>,
+}
+
+impl<'t> RequestHeaders<'t> {
+    /// Read the headers of a request. `header` should get the value of the
+    /// header with the given lowercase name.
+    pub fn from_fn(header: impl Fn(&'static str) -> Option<&'t str>) -> Self {
+        RequestHeaders {
+            accept_encoding: header("accept-encoding"),
+            conditional: ConditionalHeaders {
+                if_match: header("if-match"),
+                if_none_match: header("if-none-match"),
+                if_modified_since: header("if-modified-since"),
+                if_unmodified_since: header("if-unmodified-since"),
+            },
+            range: header("range"),
+            if_range: header("if-range"),
+        }
+    }
+}
+
+/// The response to send for a file.
+#[derive(Debug)]
+pub struct FileResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, Cow<'static, str>)>,
+    pub body: Cow<'static, [u8]>,
+}
+
+impl FileResponse {
+    fn empty(status: u16) -> Self {
+        FileResponse {
+            status,
+            headers: Vec::new(),
+            body: Cow::Borrowed(&[]),
+        }
+    }
+
+    pub fn not_found() -> Self {
+        Self::empty(404)
+    }
+
+    /// Redirect a directory path that is missing the trailing slash. The
+    /// `request_path` is the path of the request as the client sent it.
+    pub fn redirect_to_directory(request_path: &str, query: Option<&str>) -> Self {
+        // A relative redirect works even when the server is mounted under
+        // some path that we don't know about.
+        let name = request_path.rsplit('/').next().unwrap_or_default();
+        let location = match query {
+            Some(query) => format!("{}/?{}", name, query),
+            None => format!("{}/", name),
+        };
+        Self::empty(301).header("Location", location)
+    }
+
+    fn header(mut self, name: &'static str, value: impl Into<Cow<'static, str>>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Add the headers describing the file, which are sent with both full and
+    /// `304 Not Modified` responses.
+    fn file_headers<F: EmbedableFile>(mut self, file: &F) -> Self {
+        self = self.header("ETag", file.etag());
+        if let Some(last_modified) = file.last_modified() {
+            self = self.header("Last-Modified", last_modified);
+        }
+        if let Some(cache_control) = file.cache_control() {
+            self = self.header("Cache-Control", cache_control);
+        }
+        self.header("Vary", "Accept-Encoding")
+    }
+}
+
+/// Respond to a request for a file, taking care of compression, cache
+/// revalidation and range requests.
+pub fn respond<F: EmbedableFile>(method: &str, headers: &RequestHeaders, file: &F) -> FileResponse {
+    let is_head = method.eq_ignore_ascii_case("HEAD");
+    if !is_head && !method.eq_ignore_ascii_case("GET") {
+        return FileResponse::empty(405).header("Allow", "GET, HEAD");
+    }
+
+    match evaluate_conditional(method, &headers.conditional, file) {
+        ConditionalResponse::Ok => {}
+        ConditionalResponse::NotModified => return FileResponse::empty(304).file_headers(file),
+        ConditionalResponse::PreconditionFailed => return FileResponse::empty(412),
+    }
+
+    let mime_type = file
+        .mime_type()
+        .unwrap_or(Cow::Borrowed("application/octet-stream"));
+    let mut response = match evaluate_range(headers.range, headers.if_range, file) {
+        RangeResponse::Full => {
+            let body = match negotiate_encoding(headers.accept_encoding, file) {
+                Some(body) => body,
+                None => return FileResponse::empty(406),
+            };
+            let mut response = FileResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: body.data,
+            }
+            .header("Content-Type", mime_type);
+            if let Some(content_encoding) = body.encoding.content_encoding() {
+                response = response.header("Content-Encoding", content_encoding);
+            }
+            response
+        }
+        RangeResponse::Partial {
+            data,
+            content_range,
+        } => FileResponse {
+            status: 206,
+            headers: Vec::new(),
+            body: data,
+        }
+        .header("Content-Type", mime_type)
+        .header("Content-Range", content_range),
+        RangeResponse::Multipart { data, content_type } => FileResponse {
+            status: 206,
+            headers: Vec::new(),
+            body: Cow::Owned(data),
+        }
+        .header("Content-Type", content_type),
+        RangeResponse::NotSatisfiable { content_range } => {
+            return FileResponse::empty(416).header("Content-Range", content_range)
+        }
+    };
+
+    let content_length = response.body.len().to_string();
+    response = response
+        .file_headers(file)
+        .header("Accept-Ranges", "bytes")
+        .header("Content-Length", content_length);
+    if is_head {
+        response.body = Cow::Borrowed(&[]);
+    }
+    response
+}